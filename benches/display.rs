@@ -0,0 +1,103 @@
+//! Compares [`SizedDefinition::to_string`], which builds a new [`String`] for
+//! every value, with [`SizedDefinition::write_to`], which writes into a
+//! reused one.
+//!
+//! There are no dependencies, so this uses its own harness:
+//!
+//! ```text
+//! cargo bench --bench display
+//! ```
+//!
+//! Each case prints the time and heap allocations per value, and fails if
+//! `write_to` allocates at all.
+
+use sized_number::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Counts every allocation, so we can prove there aren't any.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The size of the buffer that's displayed, in bytes
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Run `f` on every value in the buffer, and return the time and number of
+/// allocations.
+fn measure(mut f: impl FnMut(u64)) -> (Duration, usize) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for position in (0..(BUFFER_SIZE as u64 - 16)).step_by(16) {
+        f(position);
+    }
+
+    (start.elapsed(), ALLOCATIONS.load(Ordering::Relaxed) - before)
+}
+
+fn main() {
+    // Something that isn't all zeroes, so the values have some digits
+    let buffer: Vec<u8> = (0..BUFFER_SIZE).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+    let context = Context::new(&buffer);
+    let count = (BUFFER_SIZE / 16) as f64;
+
+    let hex = SizedDisplay::Hex(HexOptions { uppercase: true, ..Default::default() });
+    let cases = [
+        ("u8 hex",           SizedDefinition::U8,                 hex),
+        ("u32le hex",        SizedDefinition::U32(Endian::Little), hex),
+        ("u64be decimal",    SizedDefinition::U64(Endian::Big),   SizedDisplay::Decimal),
+        ("i32be binary",     SizedDefinition::I32(Endian::Big),   SizedDisplay::Binary(Default::default())),
+        ("u32pdp octal",     SizedDefinition::U32(Endian::LittleWordSwapped), SizedDisplay::Octal(Default::default())),
+        ("f64le scientific", SizedDefinition::F64(Endian::Little), SizedDisplay::Scientific(ScientificOptions { uppercase: true })),
+        ("f32be hex float",  SizedDefinition::F32(Endian::Big),   SizedDisplay::Hex(HexOptions { hex_float: true, ..Default::default() })),
+        ("uleb128 decimal",  SizedDefinition::ULEB128,            SizedDisplay::Decimal),
+    ];
+
+    println!("{:<18} {:>14} {:>14} {:>12} {:>12}", "", "to_string", "write_to", "allocs", "allocs");
+    println!("{:<18} {:>14} {:>14} {:>12} {:>12}", "", "(ns/value)", "(ns/value)", "to_string", "write_to");
+
+    for &(name, definition, display) in &cases {
+        let (to_string_time, to_string_allocations) = measure(|position| {
+            black_box(definition.to_string(context.at(position), display).unwrap());
+        });
+
+        let mut out = String::with_capacity(256);
+        let (write_to_time, write_to_allocations) = measure(|position| {
+            out.clear();
+            definition.write_to(context.at(position), display, &mut out).unwrap();
+            black_box(&out);
+        });
+
+        println!("{:<18} {:>14.1} {:>14.1} {:>12.2} {:>12.2}",
+            name,
+            to_string_time.as_nanos() as f64 / count,
+            write_to_time.as_nanos() as f64 / count,
+            to_string_allocations as f64 / count,
+            write_to_allocations as f64 / count,
+        );
+
+        assert_eq!(0, write_to_allocations, "write_to allocated while displaying {}", name);
+    }
+}
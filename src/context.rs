@@ -1,6 +1,12 @@
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
-use simple_error::{SimpleError, SimpleResult, bail};
-use std::io::{Cursor, Read};
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use half::{bf16, f16};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Cursor;
+use std::str::FromStr;
+
+use crate::float::{Float80, Float128};
+use crate::{SizedArray, SizedDefinition, SizedError, SizedResult, SizedValue};
 
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Deserialize};
@@ -11,8 +17,12 @@ pub const MAX_UTF8_BYTES: usize = 4;
 /// The maximum number of 2-byte words in a UTF16 character
 pub const MAX_UTF16_WORDS: usize = 2;
 
+/// The maximum size of a variable-length integer (enough for 64 bits, 7 bits
+/// at a time)
+pub const MAX_VARINT_BYTES: usize = 10;
+
 /// Define the endianness for reading multi-byte integers
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Endian {
     /// Most significant byte is first (eg, `0x1234` -> `12 34`)
@@ -20,43 +30,248 @@ pub enum Endian {
 
     /// Most significant byte is last (eg, `0x1234` -> `34 12`)
     Little,
+
+    /// Big endian, with the two halves of the value swapped (eg,
+    /// `0x12345678` -> `56 78 12 34`). This is how many Modbus devices store
+    /// 32-bit values.
+    BigWordSwapped,
+
+    /// Little endian, with the two halves of the value swapped (eg,
+    /// `0x12345678` -> `34 12 78 56`). This is PDP-11 (aka, middle-endian)
+    /// order for 32-bit values, and ARM FPA order for doubles.
+    LittleWordSwapped,
+
+    /// Whichever of [`Endian::Big`] or [`Endian::Little`] the host uses
+    /// (see [`Endian::resolve`]).
+    Native,
+}
+
+impl Endian {
+    /// Replace [`Endian::Native`] with the host's byte order; other orders
+    /// are unchanged.
+    ///
+    /// ```
+    /// use sized_number::Endian;
+    ///
+    /// assert_eq!(Endian::Big, Endian::Big.resolve());
+    /// assert_ne!(Endian::Native, Endian::Native.resolve());
+    /// ```
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Native if cfg!(target_endian = "big") => Self::Big,
+            Self::Native => Self::Little,
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for Endian {
+    /// Display the endian as the short suffix used in type names (`be`,
+    /// `le`, `bews`, `lews`, or `ne`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Big               => write!(f, "be"),
+            Self::Little            => write!(f, "le"),
+            Self::BigWordSwapped    => write!(f, "bews"),
+            Self::LittleWordSwapped => write!(f, "lews"),
+            Self::Native            => write!(f, "ne"),
+        }
+    }
+}
+
+impl FromStr for Endian {
+    type Err = SizedError;
+
+    /// Parse an endian from a name like `be`, `little`, `big-endian`, or
+    /// `pdp` (case insensitive).
+    fn from_str(s: &str) -> SizedResult<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "be" | "big"    | "big-endian"    | "bigendian"    | "network" => Ok(Self::Big),
+            "le" | "little" | "little-endian" | "littleendian"             => Ok(Self::Little),
+            "bews" | "big-word-swapped"    | "cdab"                        => Ok(Self::BigWordSwapped),
+            "lews" | "little-word-swapped" | "pdp" | "pdp-endian" | "middle-endian" => Ok(Self::LittleWordSwapped),
+            "ne" | "native" | "host"                                       => Ok(Self::Native),
+            _ => Err(SizedError::parse(s, "Unknown endian")),
+        }
+    }
+}
+
+/// Convert the bytes of a value in any order to big-endian order, or back
+/// again - the conversion is its own inverse.
+///
+/// Only even sizes can be word-swapped.
+pub(crate) fn reorder_bytes(bytes: &mut [u8], endian: Endian) -> SizedResult<()> {
+    let endian = endian.resolve();
+
+    if matches!(endian, Endian::BigWordSwapped | Endian::LittleWordSwapped) {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(SizedError::InvalidArgument(format!("Word-swapped values must be an even number of bytes: {}", bytes.len())));
+        }
+        bytes.rotate_left(bytes.len() / 2);
+    }
+
+    if matches!(endian, Endian::Little | Endian::LittleWordSwapped) {
+        bytes.reverse();
+    }
+
+    Ok(())
+}
+
+/// An internal function to get the value of `bytes` (up to 16 of them) in
+/// any order.
+fn decode_ordered(bytes: &[u8], endian: Endian) -> SizedResult<u128> {
+    let mut buffer = [0; 16];
+    let buffer = &mut buffer[..bytes.len()];
+    buffer.copy_from_slice(bytes);
+    reorder_bytes(buffer, endian)?;
+
+    Ok(buffer.iter().fold(0, |acc, &b| (acc << 8) | b as u128))
+}
+
+/// Generates a function that reads `count` consecutive values, decoding
+/// them all from one slice.
+macro_rules! read_array {
+    ($name:ident, $t:ty, $size:expr, $read_into:ident, $from_bits:expr) => {
+        #[doc = concat!("Read `count` consecutive [`", stringify!($t), "`] values.")]
+        ///
+        /// This is much faster than reading them one at a time, since the
+        /// bounds are only checked once.
+        pub fn $name(self, endian: Endian, count: usize) -> SizedResult<Vec<$t>> {
+            let bytes = self.slice(count.saturating_mul($size))?;
+            let mut v: Vec<$t> = vec![Default::default(); count];
+
+            match endian.resolve() {
+                Endian::Big    => BigEndian::$read_into(bytes, &mut v),
+                Endian::Little => LittleEndian::$read_into(bytes, &mut v),
+                endian => {
+                    for (value, chunk) in v.iter_mut().zip(bytes.chunks_exact($size)) {
+                        *value = $from_bits(decode_ordered(chunk, endian)?);
+                    }
+                },
+            };
+
+            Ok(v)
+        }
+    };
+}
+
+/// Make sure an arbitrary-width integer is between 1 and 16 bytes, which is
+/// all that fits in a [`u128`].
+pub(crate) fn check_width(bytes: u8) -> SizedResult<()> {
+    if bytes == 0 || bytes > 16 {
+        return Err(SizedError::InvalidArgument(format!("Integer width must be 1 to 16 bytes: {}", bytes)));
+    }
+
+    Ok(())
+}
+
+/// A mapping from file offsets to addresses, such as a loaded ELF segment -
+/// see [`Context::with_segments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Segment {
+    /// The address that the segment is mapped at
+    pub address: u64,
+
+    /// The offset of the segment in the buffer
+    pub offset: u64,
+
+    /// The size of the segment, in bytes
+    pub size: u64,
+}
+
+impl Segment {
+    /// An internal function to convert an offset to an address, if it's in
+    /// the segment.
+    fn offset_to_address(self, offset: u64) -> Option<u64> {
+        match offset.checked_sub(self.offset) {
+            Some(delta) if delta < self.size => self.address.checked_add(delta),
+            _ => None,
+        }
+    }
+
+    /// An internal function to convert an address to an offset, if it's in
+    /// the segment.
+    fn address_to_offset(self, address: u64) -> Option<u64> {
+        match address.checked_sub(self.address) {
+            Some(delta) if delta < self.size => self.offset.checked_add(delta),
+            _ => None,
+        }
+    }
 }
 
 /// A structure to hold a data structure and a position while reading the data.
 ///
 /// This is essentially a [`Cursor`], but with some convenience functions to
 /// clone and set the position more quickly.
+///
+/// The data is borrowed as a plain byte slice, so anything that can be
+/// viewed as `[u8]` - a [`Vec`], an array, part of a memory-mapped file -
+/// can be read without copying it.
+///
+/// A context can also map positions to addresses, with a base address or a
+/// list of segments - see [`Context::at_address`].
 #[derive(Debug, Clone, Copy)]
 pub struct Context<'a> {
-    v: &'a Vec<u8>,
+    v: &'a [u8],
     position: u64,
-    //c: Cursor<&'a Vec<u8>>,
+    //c: Cursor<&'a [u8]>,
+
+    /// The offset of `v` in the original buffer (non-zero for windows)
+    origin: u64,
+
+    /// The address of offset 0, if there are no segments
+    base_address: u64,
+
+    /// The segments that map offsets to addresses
+    segments: &'a [Segment],
 }
 
 impl<'a> Context<'a> {
     /// Create a new [`Context`] at position 0.
     ///
-    /// Cannot fail, even if the Vec is empty.
-    pub fn new(v: &'a Vec<u8>) -> Self {
+    /// Cannot fail, even if the buffer is empty.
+    ///
+    /// ```
+    /// use sized_number::{Context, Endian};
+    ///
+    /// let buffer = b"\x00\x01\x02\x03".to_vec();
+    ///
+    /// assert_eq!(0x0203, Context::new(&buffer[2..]).read_u16(Endian::Big).unwrap());
+    /// assert_eq!(0x0302, Context::new(b"\x02\x03").read_u16(Endian::Little).unwrap());
+    /// ```
+    pub fn new<T>(v: &'a T) -> Self
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
         Self {
-            v: v,
+            v: v.as_ref(),
             position: 0,
             //c: Cursor::new(v)
+            origin: 0,
+            base_address: 0,
+            segments: &[],
         }
     }
 
     /// Create a new [`Context`] at a given position.
     ///
-    /// Cannot fail, even if the Vec is empty or if the position is crazy. Those
-    /// are checked when using the cursor, not while creating it.
-    pub fn new_at(v: &'a Vec<u8>, position: u64) -> Self {
+    /// Cannot fail, even if the buffer is empty or if the position is crazy.
+    /// Those are checked when using the cursor, not while creating it.
+    pub fn new_at<T>(v: &'a T, position: u64) -> Self
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
         //let mut c = Cursor::new(v);
         //c.set_position(position);
 
         Self {
-            v: v,
-            position: position,
+            v: v.as_ref(),
+            position,
             //c: c
+            origin: 0,
+            base_address: 0,
+            segments: &[],
         }
     }
 
@@ -65,7 +280,7 @@ impl<'a> Context<'a> {
     /// This is for internal use only. We clone a lot while reading values, but
     /// this operation is reasonably inexpensive since we don't actually clone
     /// the data - just a reference.
-    fn cursor(self) -> Cursor<&'a Vec<u8>> {
+    fn cursor(self) -> Cursor<&'a [u8]> {
         let mut cursor = Cursor::new(self.v);
         cursor.set_position(self.position);
 
@@ -89,178 +304,507 @@ impl<'a> Context<'a> {
         self.position
     }
 
-    pub fn read_u8(self) -> SimpleResult<u8> {
+    /// Create a child [`Context`] over `len` bytes starting at `offset` (in
+    /// this context's buffer), positioned at its start.
+    ///
+    /// Positions in the child are relative to the window, and reads can't go
+    /// past either end of it - so a parser for one embedded structure can't
+    /// wander into its neighbour. The window must fit in the buffer.
+    /// Addresses are unchanged - the window's first byte has the same
+    /// address as it does in the parent.
+    ///
+    /// ```
+    /// use sized_number::{Context, Endian};
+    ///
+    /// let buffer = b"\x00\x01\x02\x03\x04\x05".to_vec();
+    /// let window = Context::new(&buffer).window(2, 2).unwrap();
+    ///
+    /// assert_eq!(0x0203, window.read_u16(Endian::Big).unwrap());
+    /// assert_eq!(0x03, window.at(1).read_u8().unwrap());
+    /// assert!(window.at(1).read_u16(Endian::Big).is_err());
+    /// ```
+    pub fn window(self, offset: u64, len: u64) -> SizedResult<Self> {
+        let end = match offset.checked_add(len) {
+            Some(end) if end <= self.v.len() as u64 => end,
+            _ => return Err(SizedError::OutOfBounds {
+                position: offset,
+                needed: len,
+                available: (self.v.len() as u64).saturating_sub(offset),
+            }),
+        };
+
+        Ok(Self {
+            v: &self.v[(offset as usize)..(end as usize)],
+            position: 0,
+            origin: self.origin + offset,
+            ..self
+        })
+    }
+
+    /// Map positions to addresses by adding a base address - for example, a
+    /// ROM that's loaded at `0x08000000`. This replaces any segments.
+    ///
+    /// ```
+    /// use sized_number::{Context, Endian};
+    ///
+    /// let buffer = b"\x00\x01\x02\x03".to_vec();
+    /// let context = Context::new(&buffer).with_base_address(0x08000000);
+    ///
+    /// assert_eq!(0x08000002, context.at(2).address().unwrap());
+    /// assert_eq!(0x0203, context.at_address(0x08000002).unwrap().read_u16(Endian::Big).unwrap());
+    /// assert!(context.at_address(0x07ffffff).is_err());
+    /// ```
+    pub fn with_base_address(self, base_address: u64) -> Self {
+        Self {
+            base_address,
+            segments: &[],
+            ..self
+        }
+    }
+
+    /// Map positions to addresses with a list of segments - for example, the
+    /// loadable segments of an ELF file. Offsets that aren't in a segment
+    /// don't have an address. This replaces any base address.
+    ///
+    /// ```
+    /// use sized_number::{Context, Segment};
+    ///
+    /// let segments = vec![
+    ///     Segment { address: 0x400000, offset: 0,  size: 16 },
+    ///     Segment { address: 0x600000, offset: 16, size: 16 },
+    /// ];
+    /// let buffer = vec![0; 32];
+    /// let context = Context::new(&buffer).with_segments(&segments);
+    ///
+    /// assert_eq!(0x600004, context.at(20).address().unwrap());
+    /// assert_eq!(20, context.at_address(0x600004).unwrap().position());
+    /// assert!(context.at_address(0x500000).is_err());
+    /// ```
+    pub fn with_segments(self, segments: &'a [Segment]) -> Self {
+        Self {
+            base_address: 0,
+            segments,
+            ..self
+        }
+    }
+
+    /// Get the address of the current position, based on the base address
+    /// or segments. By default, the address is the same as the position.
+    ///
+    /// Positions that aren't in a segment are an error.
+    pub fn address(self) -> SizedResult<u64> {
+        let offset = match self.origin.checked_add(self.position) {
+            Some(offset) => offset,
+            None => return Err(SizedError::UnmappedPosition { position: self.position }),
+        };
+
+        let address = match self.segments.is_empty() {
+            true  => self.base_address.checked_add(offset),
+            false => self.segments.iter().find_map(|s| s.offset_to_address(offset)),
+        };
+
+        match address {
+            Some(address) => Ok(address),
+            None => Err(SizedError::UnmappedPosition { position: self.position }),
+        }
+    }
+
+    /// Clone the [`Context`] and move to an address, based on the base
+    /// address or segments (see [`Context::address`]).
+    ///
+    /// Addresses that aren't mapped to this buffer are an error.
+    pub fn at_address(self, address: u64) -> SizedResult<Self> {
+        let offset = match self.segments.is_empty() {
+            true  => address.checked_sub(self.base_address),
+            false => self.segments.iter().find_map(|s| s.address_to_offset(address)),
+        };
+
+        match offset.and_then(|offset| offset.checked_sub(self.origin)) {
+            Some(position) if position < self.v.len() as u64 => Ok(self.at(position)),
+            _ => Err(SizedError::UnmappedAddress { address }),
+        }
+    }
+
+    /// An internal function to get the `size` bytes at the current position,
+    /// without copying them.
+    fn slice(self, size: usize) -> SizedResult<&'a [u8]> {
+        let start = self.position as usize;
+
+        match start.checked_add(size).and_then(|end| self.v.get(start..end)) {
+            Some(bytes) => Ok(bytes),
+            None => Err(self.out_of_bounds(size as u64)),
+        }
+    }
+
+    /// An internal function to describe a read of `needed` bytes that goes
+    /// off the end of the buffer.
+    fn out_of_bounds(self, needed: u64) -> SizedError {
+        SizedError::OutOfBounds {
+            position: self.position,
+            needed,
+            available: (self.v.len() as u64).saturating_sub(self.position),
+        }
+    }
+
+    /// An internal function to read a value of `size` (up to 16) bytes in
+    /// any order, for the orders and sizes that `byteorder` doesn't handle.
+    fn read_ordered(self, size: usize, endian: Endian) -> SizedResult<u128> {
+        decode_ordered(self.slice(size)?, endian)
+    }
+
+    pub fn read_u8(self) -> SizedResult<u8> {
         match self.cursor().read_u8() {
             Ok(i) => Ok(i),
-            Err(e) => Err(SimpleError::from(e)),
+            Err(_) => Err(self.out_of_bounds(1)),
         }
     }
 
-    pub fn read_u16(self, endian: Endian) -> SimpleResult<u16> {
+    pub fn read_u16(self, endian: Endian) -> SizedResult<u16> {
         match endian {
             Endian::Big => match self.cursor().read_u16::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(2)),
             },
             Endian::Little => match self.cursor().read_u16::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(2)),
+            },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(2, endian)? as u16),
+        }
+    }
+
+    /// Read an unsigned 24-bit (3-byte) integer.
+    pub fn read_u24(self, endian: Endian) -> SizedResult<u32> {
+        match endian {
+            Endian::Big => match self.cursor().read_u24::<BigEndian>() {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(3)),
             },
+            Endian::Little => match self.cursor().read_u24::<LittleEndian>() {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(3)),
+            },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(3, endian)? as u32),
         }
     }
 
-    pub fn read_u32(self, endian: Endian) -> SimpleResult<u32> {
+    pub fn read_u32(self, endian: Endian) -> SizedResult<u32> {
         match endian {
             Endian::Big => match self.cursor().read_u32::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(4)),
             },
             Endian::Little => match self.cursor().read_u32::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(4)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(4, endian)? as u32),
         }
     }
 
-    pub fn read_u64(self, endian: Endian) -> SimpleResult<u64> {
+    pub fn read_u64(self, endian: Endian) -> SizedResult<u64> {
         match endian {
             Endian::Big => match self.cursor().read_u64::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(8)),
             },
             Endian::Little => match self.cursor().read_u64::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(8)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(8, endian)? as u64),
         }
     }
 
-    pub fn read_u128(self, endian: Endian) -> SimpleResult<u128> {
+    pub fn read_u128(self, endian: Endian) -> SizedResult<u128> {
         match endian {
             Endian::Big => match self.cursor().read_u128::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(16)),
             },
             Endian::Little => match self.cursor().read_u128::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(16)),
+            },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => self.read_ordered(16, endian),
+        }
+    }
+
+    /// Read an unsigned integer of any width from 1 to 16 bytes.
+    pub fn read_uint(self, bytes: u8, endian: Endian) -> SizedResult<u128> {
+        check_width(bytes)?;
+
+        match endian {
+            Endian::Big => match self.cursor().read_uint128::<BigEndian>(bytes as usize) {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(bytes as u64)),
             },
+            Endian::Little => match self.cursor().read_uint128::<LittleEndian>(bytes as usize) {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(bytes as u64)),
+            },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => self.read_ordered(bytes as usize, endian),
         }
     }
 
-    pub fn read_i8(self) -> SimpleResult<i8> {
+    pub fn read_i8(self) -> SizedResult<i8> {
         match self.cursor().read_i8() {
             Ok(i) => Ok(i),
-            Err(e) => Err(SimpleError::from(e)),
+            Err(_) => Err(self.out_of_bounds(1)),
         }
     }
 
-    pub fn read_i16(self, endian: Endian) -> SimpleResult<i16> {
+    pub fn read_i16(self, endian: Endian) -> SizedResult<i16> {
         match endian {
             Endian::Big => match self.cursor().read_i16::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(2)),
             },
             Endian::Little => match self.cursor().read_i16::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(2)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(2, endian)? as i16),
         }
     }
 
-    pub fn read_i32(self, endian: Endian) -> SimpleResult<i32> {
+    /// Read a signed 24-bit (3-byte) integer, extending the sign.
+    pub fn read_i24(self, endian: Endian) -> SizedResult<i32> {
+        match endian {
+            Endian::Big => match self.cursor().read_i24::<BigEndian>() {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(3)),
+            },
+            Endian::Little => match self.cursor().read_i24::<LittleEndian>() {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(3)),
+            },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(((self.read_ordered(3, endian)? as i32) << 8) >> 8),
+        }
+    }
+
+    pub fn read_i32(self, endian: Endian) -> SizedResult<i32> {
         match endian {
             Endian::Big => match self.cursor().read_i32::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(4)),
             },
             Endian::Little => match self.cursor().read_i32::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(4)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(4, endian)? as i32),
         }
     }
 
-    pub fn read_i64(self, endian: Endian) -> SimpleResult<i64> {
+    pub fn read_i64(self, endian: Endian) -> SizedResult<i64> {
         match endian {
             Endian::Big => match self.cursor().read_i64::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(8)),
             },
             Endian::Little => match self.cursor().read_i64::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(8)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(8, endian)? as i64),
         }
     }
 
-    pub fn read_i128(self, endian: Endian) -> SimpleResult<i128> {
+    pub fn read_i128(self, endian: Endian) -> SizedResult<i128> {
         match endian {
             Endian::Big => match self.cursor().read_i128::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(16)),
             },
             Endian::Little => match self.cursor().read_i128::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(16)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(self.read_ordered(16, endian)? as i128),
         }
     }
 
-    pub fn read_f32(self, endian: Endian) -> SimpleResult<f32> {
+    /// Read a signed integer of any width from 1 to 16 bytes, extending the
+    /// sign.
+    pub fn read_int(self, bytes: u8, endian: Endian) -> SizedResult<i128> {
+        check_width(bytes)?;
+
+        match endian {
+            Endian::Big => match self.cursor().read_int128::<BigEndian>(bytes as usize) {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(bytes as u64)),
+            },
+            Endian::Little => match self.cursor().read_int128::<LittleEndian>(bytes as usize) {
+                Ok(i) => Ok(i),
+                Err(_) => Err(self.out_of_bounds(bytes as u64)),
+            },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(((self.read_ordered(bytes as usize, endian)? as i128) << (128 - bytes as u32 * 8)) >> (128 - bytes as u32 * 8)),
+        }
+    }
+
+    /// Read an IEEE-754 binary16 (half precision) float.
+    pub fn read_f16(self, endian: Endian) -> SizedResult<f16> {
+        Ok(f16::from_bits(self.read_u16(endian)?))
+    }
+
+    /// Read a bfloat16 float (the top 16 bits of a single precision float).
+    pub fn read_bf16(self, endian: Endian) -> SizedResult<bf16> {
+        Ok(bf16::from_bits(self.read_u16(endian)?))
+    }
+
+    pub fn read_f32(self, endian: Endian) -> SizedResult<f32> {
         match endian {
             Endian::Big => match self.cursor().read_f32::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(4)),
             },
             Endian::Little => match self.cursor().read_f32::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(4)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(f32::from_bits(self.read_ordered(4, endian)? as u32)),
         }
     }
 
-    pub fn read_f64(self, endian: Endian) -> SimpleResult<f64> {
+    pub fn read_f64(self, endian: Endian) -> SizedResult<f64> {
         match endian {
             Endian::Big => match self.cursor().read_f64::<BigEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(8)),
             },
             Endian::Little => match self.cursor().read_f64::<LittleEndian>() {
                 Ok(i) => Ok(i),
-                Err(e) => Err(SimpleError::from(e)),
+                Err(_) => Err(self.out_of_bounds(8)),
             },
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => Ok(f64::from_bits(self.read_ordered(8, endian)? as u64)),
         }
     }
 
-    pub fn read_bytes(self, size: usize) -> SimpleResult<Vec<u8>> {
-        let mut v: Vec<u8> = Vec::with_capacity(size);
+    /// Read an x87 80-bit extended precision float (10 bytes).
+    pub fn read_f80(self, endian: Endian) -> SizedResult<Float80> {
+        Ok(Float80::from_bits(self.read_ordered(10, endian)?))
+    }
 
-        match self.cursor().take(size as u64).read_to_end(&mut v) {
-            Ok(read_size) => {
-                if read_size < size {
-                    bail!("Read past end of buffer");
-                }
+    /// Read an IEEE-754 binary128 (quad precision) float.
+    pub fn read_f128(self, endian: Endian) -> SizedResult<Float128> {
+        Ok(Float128::from_bits(self.read_u128(endian)?))
+    }
 
-                Ok(v)
-            }
-            Err(e) => Err(SimpleError::from(e)),
+    /// An internal function to read the bytes of a base-128 varint, up to
+    /// and including the first byte without its high bit set.
+    fn read_varint_bytes(self) -> SizedResult<&'a [u8]> {
+        let available = self.v.get((self.position as usize)..).unwrap_or_default();
+
+        match available.iter().take(MAX_VARINT_BYTES).position(|b| b & 0x80 == 0) {
+            Some(i) => Ok(&available[..=i]),
+            None if available.len() < MAX_VARINT_BYTES => Err(self.out_of_bounds(available.len() as u64 + 1)),
+            None => Err(SizedError::InvalidVarint { position: self.position, bytes: available[..MAX_VARINT_BYTES].to_vec() }),
+        }
+    }
+
+    /// Read an unsigned LEB128 integer (used by DWARF, WebAssembly, DEX, and
+    /// protobuf, where it's called a varint), and return the size (in bytes)
+    /// and value.
+    ///
+    /// The value is stored 7 bits at a time, least significant first, with
+    /// the high bit of each byte set if another byte follows. Values that
+    /// don't fit in a [`u64`] are an error.
+    pub fn read_uleb128(self) -> SizedResult<(usize, u64)> {
+        let v = self.read_varint_bytes()?;
+
+        let mut value: u128 = 0;
+        for (i, b) in v.iter().enumerate() {
+            value |= ((b & 0x7f) as u128) << (i * 7);
+        }
+
+        match u64::try_from(value) {
+            Ok(value) => Ok((v.len(), value)),
+            Err(_) => Err(SizedError::InvalidVarint { position: self.position, bytes: v.to_vec() }),
+        }
+    }
+
+    /// Read a signed LEB128 integer, and return the size (in bytes) and
+    /// value.
+    ///
+    /// This is the same as [`Context::read_uleb128`], except that the sign is
+    /// extended from the top bit of the final byte.
+    pub fn read_sleb128(self) -> SizedResult<(usize, i64)> {
+        let v = self.read_varint_bytes()?;
+
+        let mut value: i128 = 0;
+        for (i, b) in v.iter().enumerate() {
+            value |= ((b & 0x7f) as i128) << (i * 7);
+        }
+
+        // Extend the sign
+        let bits = v.len() * 7;
+        if v[v.len() - 1] & 0x40 != 0 {
+            value |= -1 << bits;
+        }
+
+        match i64::try_from(value) {
+            Ok(value) => Ok((v.len(), value)),
+            Err(_) => Err(SizedError::InvalidVarint { position: self.position, bytes: v.to_vec() }),
         }
     }
 
+    /// Read a protobuf zigzag-encoded signed integer (`sint32` / `sint64`),
+    /// and return the size (in bytes) and value.
+    ///
+    /// The value is an unsigned LEB128 integer with the sign moved to the
+    /// bottom bit, so small negative numbers stay small (`0 -> 0`,
+    /// `-1 -> 1`, `1 -> 2`, `-2 -> 3`, ...).
+    pub fn read_zigzag(self) -> SizedResult<(usize, i64)> {
+        let (size, value) = self.read_uleb128()?;
+
+        Ok((size, (value >> 1) as i64 ^ -((value & 1) as i64)))
+    }
+
+    /// Read a Git-style varint (used in pack files for delta offsets), and
+    /// return the size (in bytes) and value.
+    ///
+    /// Unlike LEB128, the most significant 7 bits come first, and each
+    /// continuation adds one before shifting so that every value has exactly
+    /// one encoding. Values that don't fit in a [`u64`] are an error.
+    pub fn read_git_varint(self) -> SizedResult<(usize, u64)> {
+        let v = self.read_varint_bytes()?;
+
+        let mut value: u64 = (v[0] & 0x7f) as u64;
+        for b in &v[1..] {
+            value = match value.checked_add(1).and_then(|value| value.checked_mul(0x80)) {
+                Some(value) => value | (b & 0x7f) as u64,
+                None => return Err(SizedError::InvalidVarint { position: self.position, bytes: v.to_vec() }),
+            };
+        }
+
+        Ok((v.len(), value))
+    }
+
+    pub fn read_bytes(self, size: usize) -> SizedResult<Vec<u8>> {
+        // Reading nothing always works, even past the end of the buffer
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Check the size before allocating anything, since it might have
+        // come from untrusted data
+        Ok(self.slice(size)?.to_vec())
+    }
+
     /// Read a UTF-8 character, and return the size (in bytes) and character.
     ///
     /// A UTF-8 character is between 1 and [`MAX_UTF8_BYTES`] bytes long. The
     /// size is automatically determined. If the character is invalid or the
     /// read would go off the end of the buffer, an error is returned.
-    pub fn read_utf8(self) -> SimpleResult<(usize, char)> {
+    pub fn read_utf8(self) -> SizedResult<(usize, char)> {
         let mut c = self.cursor();
 
         let mut v: Vec<u8> = Vec::new();
         for i in 1..=MAX_UTF8_BYTES {
             v.push(match c.read_u8() {
                 Ok(i) => i,
-                Err(e) => bail!("Couldn't read UTF-8: {}", e),
+                Err(_) => return Err(self.out_of_bounds(i as u64)),
             });
 
             if let Ok(s) = std::str::from_utf8(&v) {
@@ -270,28 +814,18 @@ impl<'a> Context<'a> {
             }
         }
 
-        bail!("Couldn't find a valid UTF-8 character");
+        Err(SizedError::InvalidUtf8 { position: self.position, bytes: v })
     }
 
     /// Read a UTF-16 character, and return the size (in bytes) and character.
     ///
     /// A UTF-16 character is between 1 and [`MAX_UTF16_WORDS`]. The size (in
     /// bytes) is automatically determined (and returned).
-    pub fn read_utf16(self, endian: Endian) -> SimpleResult<(usize, char)> {
-        let mut c = self.cursor();
+    pub fn read_utf16(self, endian: Endian) -> SizedResult<(usize, char)> {
         let mut v: Vec<u16> = Vec::new();
         for i in 1..=MAX_UTF16_WORDS {
             // Read 16 bits in the proper endian
-            v.push(match endian {
-                Endian::Big => match c.read_u16::<BigEndian>() {
-                    Ok(i) => i,
-                    Err(e) => bail!("Couldn't read UTF-16: {}", e),
-                },
-                Endian::Little => match c.read_u16::<LittleEndian>() {
-                    Ok(i) => i,
-                    Err(e) => bail!("Couldn't read UTF-16: {}", e),
-                },
-            });
+            v.push(self.at(self.position + (i as u64 - 1) * 2).read_u16(endian)?);
 
             // Attempt to convert to a string
             if let Ok(s) = String::from_utf16(&v) {
@@ -301,20 +835,132 @@ impl<'a> Context<'a> {
             }
         }
 
-        bail!("Couldn't find a valid UTF-16 character");
+        Err(SizedError::InvalidUtf16 { position: self.position, words: v })
     }
 
     /// Read a UTF-32 character, and return the size (in bytes) and character.
     ///
     /// A UTF-32 character is always 4 bytes (32-bits) long, so we don't return
     /// the size.
-    pub fn read_utf32(self, endian: Endian) -> SimpleResult<char> {
-        match char::from_u32(self.read_u32(endian)?) {
+    pub fn read_utf32(self, endian: Endian) -> SizedResult<char> {
+        let value = self.read_u32(endian)?;
+
+        match char::from_u32(value) {
             Some(c) => Ok(c),
-            None    => bail!("Couldn't find a valid UTF-32 character"),
+            None    => Err(SizedError::InvalidUtf32 { position: self.position, value }),
         }
     }
 
+    /// An internal function to move forward after a successful read.
+    fn advance(&mut self, size: usize) -> SizedResult<()> {
+        self.position = match self.position.checked_add(size as u64) {
+            Some(position) => position,
+            None => return Err(SizedError::InvalidArgument("Position overflowed".to_string())),
+        };
+
+        Ok(())
+    }
+
+    /// Read a value, based on a [`SizedDefinition`], and move past it.
+    ///
+    /// Unlike the other `read_*` functions, this changes the position (by the
+    /// size of the value, which might depend on the data). If the read fails,
+    /// the position doesn't change.
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x01\x00\x02\xe5\x8e\x26".to_vec();
+    /// let mut context = Context::new(&buffer);
+    ///
+    /// assert_eq!(SizedValue::U8(1),        context.read_next(SizedDefinition::U8).unwrap());
+    /// assert_eq!(SizedValue::U16(2),       context.read_next(SizedDefinition::U16(Endian::Big)).unwrap());
+    /// assert_eq!(SizedValue::U64(624485),  context.read_next(SizedDefinition::ULEB128).unwrap());
+    /// assert_eq!(6, context.position());
+    /// ```
+    pub fn read_next(&mut self, definition: SizedDefinition) -> SizedResult<SizedValue> {
+        let (size, value) = definition.read_sized(*self)?;
+        self.advance(size)?;
+
+        Ok(value)
+    }
+
+    /// Read `size` bytes, and move past them.
+    pub fn read_next_bytes(&mut self, size: usize) -> SizedResult<Vec<u8>> {
+        let v = self.read_bytes(size)?;
+        self.advance(size)?;
+
+        Ok(v)
+    }
+
+    /// Read a UTF-8 character, and move past it - see [`Context::read_utf8`].
+    pub fn read_next_utf8(&mut self) -> SizedResult<char> {
+        let (size, c) = self.read_utf8()?;
+        self.advance(size)?;
+
+        Ok(c)
+    }
+
+    /// Read a UTF-16 character, and move past it - see
+    /// [`Context::read_utf16`].
+    pub fn read_next_utf16(&mut self, endian: Endian) -> SizedResult<char> {
+        let (size, c) = self.read_utf16(endian)?;
+        self.advance(size)?;
+
+        Ok(c)
+    }
+
+    /// Read a UTF-32 character, and move past it.
+    pub fn read_next_utf32(&mut self, endian: Endian) -> SizedResult<char> {
+        let c = self.read_utf32(endian)?;
+        self.advance(4)?;
+
+        Ok(c)
+    }
+
+    /// Read `count` consecutive values of any type, based on a
+    /// [`SizedDefinition`] - see [`SizedDefinition::read_array`].
+    pub fn read_array(self, definition: SizedDefinition, count: usize) -> SizedResult<SizedArray> {
+        definition.read_array(self, count)
+    }
+
+    /// Read `count` consecutive [`u8`] values.
+    pub fn read_u8_array(self, count: usize) -> SizedResult<Vec<u8>> {
+        Ok(self.slice(count)?.to_vec())
+    }
+
+    /// Read `count` consecutive [`i8`] values.
+    pub fn read_i8_array(self, count: usize) -> SizedResult<Vec<i8>> {
+        Ok(self.slice(count)?.iter().map(|&b| b as i8).collect())
+    }
+
+    read_array!(read_u16_array,  u16,  2,  read_u16_into,  |bits| bits as u16);
+    read_array!(read_u32_array,  u32,  4,  read_u32_into,  |bits| bits as u32);
+    read_array!(read_u64_array,  u64,  8,  read_u64_into,  |bits| bits as u64);
+    read_array!(read_u128_array, u128, 16, read_u128_into, |bits| bits);
+    read_array!(read_i16_array,  i16,  2,  read_i16_into,  |bits| bits as i16);
+    read_array!(read_i32_array,  i32,  4,  read_i32_into,  |bits| bits as i32);
+    read_array!(read_i64_array,  i64,  8,  read_i64_into,  |bits| bits as i64);
+    read_array!(read_i128_array, i128, 16, read_i128_into, |bits| bits as i128);
+    read_array!(read_f32_array,  f32,  4,  read_f32_into,  |bits| f32::from_bits(bits as u32));
+    read_array!(read_f64_array,  f64,  8,  read_f64_into,  |bits| f64::from_bits(bits as u64));
+
+    /// An internal function to split the `count * size` bytes at the current
+    /// position into a [`Context`] for each value, checking the bounds once.
+    ///
+    /// Each chunk is a window (see [`Context::window`]), so it keeps the
+    /// parent's addresses.
+    pub(crate) fn chunks(self, size: usize, count: usize) -> SizedResult<impl Iterator<Item = Context<'a>>> {
+        let start = self.origin + self.position;
+
+        Ok(self.slice(count.saturating_mul(size))?.chunks_exact(size).enumerate().map(move |(i, v)| Self {
+            v,
+            position: 0,
+            origin: start + (i * size) as u64,
+            ..self
+        }))
+    }
+
     /// Get a [`u8`] slice starting at the current `position`
     pub fn as_slice(self) -> &'a [u8] {
         &self.v[(self.position as usize)..]
@@ -348,6 +994,20 @@ mod tests {
         // Not valid
         assert!(Context::new(&data).read_bytes(5).is_err());
         assert!(Context::new_at(&data, 5).read_bytes(1).is_err());
+        assert_eq!(Err(SizedError::OutOfBounds { position: 0, needed: u64::MAX, available: 4 }), Context::new(&data).read_bytes(usize::MAX));
+        assert!(Context::new_at(&data, 2).read_bytes(usize::MAX).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_half() -> SimpleResult<()> {
+        let data = b"\x3e\x00\x00\xc0\x3f\xc0".to_vec();
+
+        assert_eq!(f16::from_f32(1.5),  Context::new_at(&data, 0).read_f16(Endian::Big)?);
+        assert_eq!(f16::from_f32(-2.0), Context::new_at(&data, 2).read_f16(Endian::Little)?);
+        assert_eq!(bf16::from_f32(1.5), Context::new_at(&data, 4).read_bf16(Endian::Big)?);
+        assert!(Context::new_at(&data, 5).read_f16(Endian::Big).is_err());
 
         Ok(())
     }
@@ -463,5 +1123,349 @@ mod tests {
 
         Ok(())
     }
-}
 
+    #[test]
+    fn test_endian_strings() -> SimpleResult<()> {
+        assert_eq!("be", Endian::Big.to_string());
+        assert_eq!("le", Endian::Little.to_string());
+
+        assert_eq!(Endian::Big,    "be".parse::<Endian>()?);
+        assert_eq!(Endian::Big,    "Big".parse::<Endian>()?);
+        assert_eq!(Endian::Big,    "big-endian".parse::<Endian>()?);
+        assert_eq!(Endian::Big,    "network".parse::<Endian>()?);
+        assert_eq!(Endian::Little, "LE".parse::<Endian>()?);
+        assert_eq!(Endian::Little, " little ".parse::<Endian>()?);
+        assert_eq!(Endian::Little, "little-endian".parse::<Endian>()?);
+        assert_eq!(Endian::LittleWordSwapped, "lews".parse::<Endian>()?);
+        assert_eq!(Endian::LittleWordSwapped, "PDP".parse::<Endian>()?);
+        assert_eq!(Endian::BigWordSwapped,    "cdab".parse::<Endian>()?);
+
+        assert_eq!(Endian::Native,            "native".parse::<Endian>()?);
+
+        for endian in [Endian::Big, Endian::Little, Endian::BigWordSwapped, Endian::LittleWordSwapped, Endian::Native] {
+            assert_eq!(endian, endian.to_string().parse::<Endian>()?);
+        }
+
+        assert!("".parse::<Endian>().is_err());
+        assert!("middle".parse::<Endian>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_odd_widths() -> SimpleResult<()> {
+        let data = b"\x01\x02\x03\xff\xff\xfe\x04\x05".to_vec();
+
+        assert_eq!(0x010203,          Context::new_at(&data, 0).read_u24(Endian::Big)?);
+        assert_eq!(0x030201,          Context::new_at(&data, 0).read_u24(Endian::Little)?);
+        assert_eq!(-2,                Context::new_at(&data, 3).read_i24(Endian::Big)?);
+        assert_eq!(-65537,            Context::new_at(&data, 3).read_i24(Endian::Little)?);
+        assert_eq!(0x010203ffff,      Context::new_at(&data, 0).read_uint(5, Endian::Big)?);
+        assert_eq!(0x0504feffff03,    Context::new_at(&data, 2).read_uint(6, Endian::Little)?);
+        assert_eq!(-2,                Context::new_at(&data, 3).read_int(3, Endian::Big)?);
+        assert_eq!(0x0102,            Context::new_at(&data, 0).read_int(2, Endian::Big)?);
+        assert_eq!(-1,                Context::new_at(&data, 3).read_int(1, Endian::Little)?);
+
+        // Too long for the buffer, or for a u128
+        assert!(Context::new_at(&data, 6).read_u24(Endian::Big).is_err());
+        assert!(Context::new_at(&data, 0).read_uint(9, Endian::Big).is_err());
+        assert!(Context::new_at(&data, 0).read_uint(0, Endian::Big).is_err());
+        assert!(Context::new_at(&data, 0).read_int(17, Endian::Big).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_word_swapped() -> SimpleResult<()> {
+        let data = b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10".to_vec();
+        let context = Context::new(&data);
+
+        let big = Endian::BigWordSwapped;
+        let little = Endian::LittleWordSwapped;
+
+        assert_eq!(0x0201,                              context.read_u16(big)?);
+        assert_eq!(0x0102,                              context.read_u16(little)?);
+        assert_eq!(0x03040102,                          context.read_u32(big)?);
+        assert_eq!(0x02010403,                          context.read_u32(little)?);
+        assert_eq!(0x0506070801020304,                  context.read_u64(big)?);
+        assert_eq!(0x0403020108070605,                  context.read_u64(little)?);
+        assert_eq!(0x090a0b0c0d0e0f100102030405060708,  context.read_u128(big)?);
+        assert_eq!(0x0807060504030201100f0e0d0c0b0a09,  context.read_u128(little)?);
+        assert_eq!(0x040506010203,                      context.read_uint(6, big)?);
+        assert_eq!(0x030201060504,                      context.read_uint(6, little)?);
+
+        // Signed values extend the sign from the right place
+        let data = b"\xff\xfe\x80\x00\x00\x80".to_vec();
+        let context = Context::new(&data);
+
+        assert_eq!(8421376,          context.at(2).read_i32(big)?);
+        assert_eq!(-16842624,        context.read_i32(little)?);
+        assert_eq!(2164260480,       context.read_int(6, big)?);
+        assert_eq!(-139642280083456, context.read_int(6, little)?);
+        assert_eq!(-257,             context.read_i16(big)?);
+
+        // ARM FPA double, and a Modbus float
+        let data = b"\x00\x00\xf0\x3f\x00\x00\x00\x00\x00\x00\x3f\xc0".to_vec();
+        assert_eq!(1.0, Context::new_at(&data, 0).read_f64(little)?);
+        assert_eq!(1.5, Context::new_at(&data, 8).read_f32(big)?);
+
+        // Odd sizes can't be swapped
+        assert!(context.read_u24(big).is_err());
+        assert!(context.read_int(3, little).is_err());
+        assert!(context.read_i24(little).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_native() -> SimpleResult<()> {
+        let data = 0x0102030405060708u64.to_ne_bytes().to_vec();
+        let context = Context::new(&data);
+
+        assert_eq!(0x0102030405060708, context.read_u64(Endian::Native)?);
+        assert_eq!(context.read_u32(Endian::Native.resolve())?, context.read_u32(Endian::Native)?);
+        assert_eq!(context.read_int(6, Endian::Native.resolve())?, context.read_int(6, Endian::Native)?);
+        assert_eq!(context.read_i24(Endian::Native.resolve())?, context.read_i24(Endian::Native)?);
+        assert_eq!(context.read_f64(Endian::Native.resolve())?, context.read_f64(Endian::Native)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_sources() -> SimpleResult<()> {
+        let data = b"\x00\x01\x02\x03\x04\x05".to_vec();
+
+        // A Vec, a slice of it, an array, and a Cow all read the same way
+        let array: [u8; 4] = [0x02, 0x03, 0x04, 0x05];
+        let cow: std::borrow::Cow<[u8]> = std::borrow::Cow::Borrowed(&data[2..]);
+
+        assert_eq!(0x02030405, Context::new_at(&data, 2).read_u32(Endian::Big)?);
+        assert_eq!(0x02030405, Context::new(&data[2..]).read_u32(Endian::Big)?);
+        assert_eq!(0x02030405, Context::new(&array).read_u32(Endian::Big)?);
+        assert_eq!(0x02030405, Context::new(&*cow).read_u32(Endian::Big)?);
+        assert_eq!(0x6968,     Context::new("hi").read_u16(Endian::Little)?);
+
+        // A slice ends where it ends, even if the underlying buffer doesn't
+        let context = Context::new(&data[0..3]);
+        assert!(context.read_u32(Endian::Big).is_err());
+        assert_eq!(vec![0x01, 0x02], context.at(1).as_slice().to_vec());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_window() -> SimpleResult<()> {
+        let data = b"\x00\x01\x02\x03\x04\x05\x06\x07".to_vec();
+        let context = Context::new_at(&data, 7);
+
+        // The offset is in the buffer, not relative to the position
+        let window = context.window(2, 4)?;
+        assert_eq!(0, window.position());
+        assert_eq!(0x02030405, window.read_u32(Endian::Big)?);
+        assert_eq!(vec![0x04, 0x05], window.at(2).as_slice().to_vec());
+        assert_eq!(vec![0x03, 0x04], window.at(1).read_bytes(2)?);
+
+        // Nothing past the end of the window
+        assert!(window.at(1).read_u32(Endian::Big).is_err());
+        assert!(window.at(4).read_u8().is_err());
+        assert!(window.at(3).read_bytes(2).is_err());
+
+        // Windows nest, and stay inside their parent
+        let inner = window.window(1, 2)?;
+        assert_eq!(0x0304, inner.read_u16(Endian::Big)?);
+        assert!(inner.at(1).read_u16(Endian::Big).is_err());
+        assert!(window.window(1, 4).is_err());
+
+        // Empty windows are fine, but windows off the end aren't
+        assert!(context.window(8, 0)?.read_u8().is_err());
+        assert!(context.window(9, 0).is_err());
+        assert!(context.window(4, u64::MAX).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunks() -> SimpleResult<()> {
+        let data = b"\x00\x01\x02\x03\x04\x05\x06\x07".to_vec();
+        let segments = vec![Segment { address: 0x8000, offset: 0, size: 8 }];
+
+        // Chunks keep the parent's addresses, even from inside a window
+        for context in [Context::new(&data).with_base_address(0x1000), Context::new(&data).with_segments(&segments)] {
+            let parent = context.window(1, 7)?.at(1);
+            let chunks: Vec<Context> = parent.chunks(2, 3)?.collect();
+
+            assert_eq!(3, chunks.len());
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                assert_eq!(parent.at(1 + i as u64 * 2).address()?, chunk.address()?);
+                assert_eq!(parent.at(1 + i as u64 * 2).read_u16(Endian::Big)?, chunk.read_u16(Endian::Big)?);
+                assert_eq!(parent.at(2 + i as u64 * 2).address()?, chunk.at(1).address()?);
+            }
+        }
+
+        assert!(Context::new(&data).at(3).chunks(2, 3).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_addresses() -> SimpleResult<()> {
+        let data = b"\x00\x01\x02\x03\x04\x05\x06\x07".to_vec();
+
+        // By default, addresses are positions
+        let context = Context::new(&data);
+        assert_eq!(5, context.at(5).address()?);
+        assert_eq!(5, context.at_address(5)?.position());
+        assert!(context.at_address(8).is_err());
+
+        // A base address
+        let context = context.with_base_address(0x1000);
+        assert_eq!(0x1003, context.at(3).address()?);
+        assert_eq!(0x04050607, context.at_address(0x1004)?.read_u32(Endian::Big)?);
+        assert!(context.at_address(0xfff).is_err());
+        assert!(context.at_address(0x1008).is_err());
+        assert!(context.with_base_address(u64::MAX).at(1).address().is_err());
+
+        // Segments - the second maps into the middle of the buffer, and the
+        // third is off the end of it
+        let segments = vec![
+            Segment { address: 0x8000, offset: 0, size: 2 },
+            Segment { address: 0x4000, offset: 4, size: 2 },
+            Segment { address: 0x9000, offset: 16, size: 16 },
+        ];
+        let context = context.with_segments(&segments);
+
+        assert_eq!(0x8001, context.at(1).address()?);
+        assert_eq!(0x4001, context.at(5).address()?);
+        assert!(context.at(2).address().is_err());
+        assert!(context.at(6).address().is_err());
+        assert_eq!(0x0405, context.at_address(0x4000)?.read_u16(Endian::Big)?);
+        assert!(context.at_address(0x4002).is_err());
+        assert!(context.at_address(0x9000).is_err());
+        assert!(context.at_address(0x1000).is_err());
+
+        // Windows keep their addresses, and only map their own bytes
+        let window = context.window(4, 2)?;
+        assert_eq!(0x4000, window.address()?);
+        assert_eq!(1, window.at_address(0x4001)?.position());
+        assert!(window.at_address(0x8000).is_err());
+
+        let window = Context::new(&data).with_base_address(0x1000).window(2, 4)?;
+        assert_eq!(0x1002, window.address()?);
+        assert_eq!(0x05, window.at_address(0x1005)?.read_u8()?);
+        assert!(window.at_address(0x1006).is_err());
+        assert!(window.at_address(0x1001).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_next() -> SimpleResult<()> {
+        let data = b"\x41\xe2\x98\x83\x00\x42\x3d\xd8\x00\xde\x00\x01\xf6\x00\x7f\x01\x12\x34".to_vec();
+        let mut context = Context::new(&data);
+
+        assert_eq!('A',         context.read_next_utf8()?);
+        assert_eq!(1,           context.position());
+        assert_eq!('☃',         context.read_next_utf8()?);
+        assert_eq!(4,           context.position());
+        assert_eq!('B',         context.read_next_utf16(Endian::Big)?);
+        assert_eq!('😀',        context.read_next_utf16(Endian::Little)?);
+        assert_eq!(10,          context.position());
+        assert_eq!('😀',        context.read_next_utf32(Endian::Big)?);
+        assert_eq!(SizedValue::I64(-1), context.read_next(SizedDefinition::SLEB128)?);
+        assert_eq!(SizedValue::I64(-1), context.read_next(SizedDefinition::ZigZag)?);
+        assert_eq!(16,          context.position());
+
+        // Failed reads don't move
+        assert!(context.read_next(SizedDefinition::U32(Endian::Big)).is_err());
+        assert!(context.read_next_bytes(3).is_err());
+        assert!(context.read_next_utf32(Endian::Big).is_err());
+        assert_eq!(16,          context.position());
+
+        assert_eq!(vec![0x12, 0x34], context.read_next_bytes(2)?);
+        assert_eq!(18,          context.position());
+        assert!(context.read_next(SizedDefinition::U8).is_err());
+
+        // Copies don't share a position
+        let mut copy = context.at(0);
+        copy.read_next(SizedDefinition::U16(Endian::Big))?;
+        assert_eq!(2,  copy.position());
+        assert_eq!(18, context.position());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_arrays() -> SimpleResult<()> {
+        let c = Context::new(b"\x00\x01\x02\x03\x04\x05\x06\x07\x3f\x80\x00\x00");
+
+        assert_eq!(vec![0x00, 0x01, 0x02],                c.read_u8_array(3)?);
+        assert_eq!(vec![0x3f, -128, 0x00],                c.at(8).read_i8_array(3)?);
+        assert_eq!(vec![0x0001, 0x0203],                  c.read_u16_array(Endian::Big, 2)?);
+        assert_eq!(vec![0x0100, 0x0302],                  c.read_u16_array(Endian::Little, 2)?);
+        assert_eq!(vec![0x02030001, 0x06070405],          c.read_u32_array(Endian::BigWordSwapped, 2)?);
+        assert_eq!(vec![0x01000302],                      c.read_u32_array(Endian::LittleWordSwapped, 1)?);
+        assert_eq!(vec![0x0001020304050607],              c.read_u64_array(Endian::Big, 1)?);
+        assert_eq!(vec![-0x7fc1],                         c.at(8).read_i16_array(Endian::Little, 1)?);
+        assert_eq!(vec![1.0],                             c.at(8).read_f32_array(Endian::Big, 1)?);
+        assert_eq!(c.read_u32_array(Endian::Native, 3)?,  c.read_u32_array(Endian::Native.resolve(), 3)?);
+        assert_eq!(Vec::<u64>::new(),                     c.read_u64_array(Endian::Big, 0)?);
+
+        assert!(c.read_u32_array(Endian::Big, 4).is_err());
+        assert!(c.at(11).read_u16_array(Endian::Big, 1).is_err());
+        assert!(c.read_u8_array(usize::MAX).is_err());
+        assert!(c.read_u64_array(Endian::Big, usize::MAX).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_varints() -> SimpleResult<()> {
+        let tests: Vec<(&[u8], usize, u64, i64)> = vec![
+            // data                                         size  uleb128               sleb128
+            (b"\x00",                                        1,    0,                    0),
+            (b"\x7f",                                        1,    127,                  -1),
+            (b"\x3f",                                        1,    63,                   63),
+            (b"\x40",                                        1,    64,                   -64),
+            (b"\xc0\x00",                                    2,    64,                   64),
+            (b"\xbf\x7f",                                    2,    16319,                -65),
+            (b"\x80\x01",                                    2,    128,                  128),
+            (b"\xe5\x8e\x26\xff",                            3,    624485,               624485),
+            (b"\xc0\xbb\x78",                                3,    1973696,              -123456),
+            (b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00",    10,   0x7fffffffffffffff,   0x7fffffffffffffff),
+        ];
+
+        for (data, size, uleb128, sleb128) in tests {
+            assert_eq!((size, uleb128), Context::new(data).read_uleb128()?);
+            assert_eq!((size, sleb128), Context::new(data).read_sleb128()?);
+        }
+
+        // The largest and smallest values
+        assert_eq!((10, u64::MAX), Context::new(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01").read_uleb128()?);
+        assert_eq!((10, i64::MIN), Context::new(b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x7f").read_sleb128()?);
+
+        // Zigzag
+        assert_eq!((1, 0),           Context::new(b"\x00").read_zigzag()?);
+        assert_eq!((1, -1),          Context::new(b"\x01").read_zigzag()?);
+        assert_eq!((1, 1),           Context::new(b"\x02").read_zigzag()?);
+        assert_eq!((1, -2),          Context::new(b"\x03").read_zigzag()?);
+        assert_eq!((5, 2147483647),  Context::new(b"\xfe\xff\xff\xff\x0f").read_zigzag()?);
+        assert_eq!((5, -2147483648), Context::new(b"\xff\xff\xff\xff\x0f").read_zigzag()?);
+
+        // Git varints
+        assert_eq!((1, 0),     Context::new(b"\x00").read_git_varint()?);
+        assert_eq!((1, 127),   Context::new(b"\x7f").read_git_varint()?);
+        assert_eq!((2, 128),   Context::new(b"\x80\x00").read_git_varint()?);
+        assert_eq!((2, 16511), Context::new(b"\xff\x7f").read_git_varint()?);
+        assert_eq!((3, 16512), Context::new(b"\x80\x80\x00").read_git_varint()?);
+
+        // Truncated, too long, and too big
+        assert!(Context::new(b"\x80").read_uleb128().is_err());
+        assert!(Context::new(b"").read_sleb128().is_err());
+        assert!(Context::new(b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x00").read_uleb128().is_err());
+        assert!(Context::new(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02").read_uleb128().is_err());
+        assert!(Context::new(b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x01").read_sleb128().is_err());
+        assert!(Context::new(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x7f").read_git_varint().is_err());
+
+        Ok(())
+    }
+}
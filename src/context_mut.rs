@@ -0,0 +1,640 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use half::{bf16, f16};
+
+use crate::context::{Context, Endian, check_width, reorder_bytes};
+use crate::float::{Float80, Float128};
+use crate::{SizedError, SizedResult};
+
+/// An internal function to write the low bytes of `value` to `buffer` in
+/// any order, for the orders that `byteorder` doesn't handle. Nothing is
+/// written if the order is invalid for the size.
+fn write_ordered(buffer: &mut [u8], value: u128, endian: Endian) -> SizedResult<()> {
+    let mut bytes = value.to_be_bytes()[(16 - buffer.len())..].to_vec();
+    reorder_bytes(&mut bytes, endian)?;
+    buffer.copy_from_slice(&bytes);
+
+    Ok(())
+}
+
+/// The writable counterpart to [`Context`].
+///
+/// Holds a mutable reference to a buffer and a position, and writes numbers
+/// into the buffer at that position. The buffer is never resized - writing
+/// past the end is an error, and nothing is written when that happens.
+///
+/// Like [`Context`], the buffer is borrowed as a plain byte slice, so a
+/// [`Vec`], an array, or any other `[u8]` can be written in place. Writing
+/// doesn't change the position.
+#[derive(Debug)]
+pub struct ContextMut<'a> {
+    v: &'a mut [u8],
+    position: u64,
+}
+
+impl<'a> ContextMut<'a> {
+    /// Create a new [`ContextMut`] at position 0.
+    ///
+    /// Cannot fail, even if the buffer is empty.
+    ///
+    /// ```
+    /// use sized_number::{ContextMut, Endian};
+    ///
+    /// let mut buffer = [0u8; 4];
+    /// ContextMut::new(&mut buffer[2..]).write_u16(0x0203, Endian::Big).unwrap();
+    ///
+    /// assert_eq!([0, 0, 2, 3], buffer);
+    /// ```
+    pub fn new<T>(v: &'a mut T) -> Self
+    where
+        T: AsMut<[u8]> + ?Sized,
+    {
+        Self {
+            v: v.as_mut(),
+            position: 0,
+        }
+    }
+
+    /// Create a new [`ContextMut`] at a given position.
+    ///
+    /// Cannot fail, even if the buffer is empty or if the position is crazy.
+    /// Those are checked when writing, not while creating it.
+    pub fn new_at<T>(v: &'a mut T, position: u64) -> Self
+    where
+        T: AsMut<[u8]> + ?Sized,
+    {
+        Self {
+            v: v.as_mut(),
+            position,
+        }
+    }
+
+    /// Change the position of the [`ContextMut`].
+    ///
+    /// Since this isn't `Copy` like [`Context`], this consumes it and returns
+    /// it at the new position.
+    pub fn at(self, new_position: u64) -> Self {
+        let mut c = self;
+        c.position = new_position;
+
+        c
+    }
+
+    /// Get the current position.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Get a read-only [`Context`] at the same position.
+    ///
+    /// Handy for reading back a value that was just written.
+    pub fn as_context(&self) -> Context<'_> {
+        Context::new_at(self.v, self.position)
+    }
+
+    /// Get the `size` bytes at the current position, or an error if they're
+    /// not all in the buffer.
+    fn buffer(&mut self, size: usize) -> SizedResult<&mut [u8]> {
+        let end = match self.position.checked_add(size as u64) {
+            Some(end) if end <= self.v.len() as u64 => end,
+            _ => return Err(SizedError::OutOfBounds {
+                position: self.position,
+                needed: size as u64,
+                available: (self.v.len() as u64).saturating_sub(self.position),
+            }),
+        };
+
+        Ok(&mut self.v[(self.position as usize)..(end as usize)])
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> SizedResult<()> {
+        self.buffer(1)?[0] = value;
+
+        Ok(())
+    }
+
+    pub fn write_u16(&mut self, value: u16, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(2)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_u16(buffer, value),
+            Endian::Little => LittleEndian::write_u16(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write an unsigned 24-bit (3-byte) integer. The value must fit in 24
+    /// bits.
+    pub fn write_u24(&mut self, value: u32, endian: Endian) -> SizedResult<()> {
+        self.write_uint(value as u128, 3, endian)
+    }
+
+    pub fn write_u32(&mut self, value: u32, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(4)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_u32(buffer, value),
+            Endian::Little => LittleEndian::write_u32(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn write_u64(&mut self, value: u64, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(8)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_u64(buffer, value),
+            Endian::Little => LittleEndian::write_u64(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn write_u128(&mut self, value: u128, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(16)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_u128(buffer, value),
+            Endian::Little => LittleEndian::write_u128(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value, endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write an unsigned integer of any width from 1 to 16 bytes. The value
+    /// must fit in that many bytes.
+    pub fn write_uint(&mut self, value: u128, bytes: u8, endian: Endian) -> SizedResult<()> {
+        check_width(bytes)?;
+        if bytes < 16 && value >> (bytes as u32 * 8) != 0 {
+            return Err(SizedError::Overflow { value: value.to_string(), bits: bytes as u32 * 8 });
+        }
+
+        let buffer = self.buffer(bytes as usize)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_uint128(buffer, value, bytes as usize),
+            Endian::Little => LittleEndian::write_uint128(buffer, value, bytes as usize),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value, endian)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn write_i8(&mut self, value: i8) -> SizedResult<()> {
+        self.write_u8(value as u8)
+    }
+
+    pub fn write_i16(&mut self, value: i16, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(2)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_i16(buffer, value),
+            Endian::Little => LittleEndian::write_i16(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write a signed 24-bit (3-byte) integer. The value must fit in 24 bits.
+    pub fn write_i24(&mut self, value: i32, endian: Endian) -> SizedResult<()> {
+        self.write_int(value as i128, 3, endian)
+    }
+
+    pub fn write_i32(&mut self, value: i32, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(4)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_i32(buffer, value),
+            Endian::Little => LittleEndian::write_i32(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn write_i64(&mut self, value: i64, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(8)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_i64(buffer, value),
+            Endian::Little => LittleEndian::write_i64(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn write_i128(&mut self, value: i128, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(16)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_i128(buffer, value),
+            Endian::Little => LittleEndian::write_i128(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write a signed integer of any width from 1 to 16 bytes. The value
+    /// must fit in that many bytes.
+    pub fn write_int(&mut self, value: i128, bytes: u8, endian: Endian) -> SizedResult<()> {
+        check_width(bytes)?;
+        let shift = 128 - bytes as u32 * 8;
+        if (value << shift) >> shift != value {
+            return Err(SizedError::Overflow { value: value.to_string(), bits: bytes as u32 * 8 });
+        }
+
+        let buffer = self.buffer(bytes as usize)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_int128(buffer, value, bytes as usize),
+            Endian::Little => LittleEndian::write_int128(buffer, value, bytes as usize),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write an IEEE-754 binary16 (half precision) float.
+    pub fn write_f16(&mut self, value: f16, endian: Endian) -> SizedResult<()> {
+        self.write_u16(value.to_bits(), endian)
+    }
+
+    /// Write a bfloat16 float.
+    pub fn write_bf16(&mut self, value: bf16, endian: Endian) -> SizedResult<()> {
+        self.write_u16(value.to_bits(), endian)
+    }
+
+    pub fn write_f32(&mut self, value: f32, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(4)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_f32(buffer, value),
+            Endian::Little => LittleEndian::write_f32(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value.to_bits() as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn write_f64(&mut self, value: f64, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(8)?;
+
+        match endian {
+            Endian::Big    => BigEndian::write_f64(buffer, value),
+            Endian::Little => LittleEndian::write_f64(buffer, value),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value.to_bits() as u128, endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write an x87 80-bit extended precision float (10 bytes).
+    pub fn write_f80(&mut self, value: Float80, endian: Endian) -> SizedResult<()> {
+        let buffer = self.buffer(10)?;
+
+        match endian {
+            Endian::Big    => buffer.copy_from_slice(&value.to_bits().to_be_bytes()[6..]),
+            Endian::Little => buffer.copy_from_slice(&value.to_bits().to_le_bytes()[..10]),
+            Endian::BigWordSwapped | Endian::LittleWordSwapped | Endian::Native => write_ordered(buffer, value.to_bits(), endian)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write an IEEE-754 binary128 (quad precision) float.
+    pub fn write_f128(&mut self, value: Float128, endian: Endian) -> SizedResult<()> {
+        self.write_u128(value.to_bits(), endian)
+    }
+
+    /// Write an unsigned LEB128 integer, and return the size (in bytes).
+    ///
+    /// The shortest encoding is always used.
+    pub fn write_uleb128(&mut self, value: u64) -> SizedResult<usize> {
+        let mut v: Vec<u8> = Vec::new();
+        let mut value = value;
+
+        loop {
+            let b = (value & 0x7f) as u8;
+            value >>= 7;
+
+            if value == 0 {
+                v.push(b);
+                break;
+            }
+            v.push(b | 0x80);
+        }
+
+        self.write_bytes(&v)?;
+        Ok(v.len())
+    }
+
+    /// Write a signed LEB128 integer, and return the size (in bytes).
+    ///
+    /// The shortest encoding is always used.
+    pub fn write_sleb128(&mut self, value: i64) -> SizedResult<usize> {
+        let mut v: Vec<u8> = Vec::new();
+        let mut value = value;
+
+        loop {
+            let b = (value & 0x7f) as u8;
+            value >>= 7;
+
+            // Stop once the rest is sign, and the sign bit is right
+            if (value == 0 && b & 0x40 == 0) || (value == -1 && b & 0x40 != 0) {
+                v.push(b);
+                break;
+            }
+            v.push(b | 0x80);
+        }
+
+        self.write_bytes(&v)?;
+        Ok(v.len())
+    }
+
+    /// Write a protobuf zigzag-encoded signed integer, and return the size
+    /// (in bytes).
+    pub fn write_zigzag(&mut self, value: i64) -> SizedResult<usize> {
+        self.write_uleb128(((value << 1) ^ (value >> 63)) as u64)
+    }
+
+    /// Write a Git-style varint, and return the size (in bytes).
+    pub fn write_git_varint(&mut self, value: u64) -> SizedResult<usize> {
+        let mut v: Vec<u8> = vec![(value & 0x7f) as u8];
+        let mut value = value >> 7;
+
+        while value != 0 {
+            value -= 1;
+            v.push(0x80 | (value & 0x7f) as u8);
+            value >>= 7;
+        }
+        v.reverse();
+
+        self.write_bytes(&v)?;
+        Ok(v.len())
+    }
+
+    /// Write raw bytes at the current position.
+    ///
+    /// Either all of the bytes are written, or (if they don't fit) none are.
+    pub fn write_bytes(&mut self, data: &[u8]) -> SizedResult<()> {
+        self.buffer(data.len())?.copy_from_slice(data);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::SimpleResult;
+
+    #[test]
+    fn test_write_unsigned() -> SimpleResult<()> {
+        let mut data = vec![0u8; 16];
+
+        ContextMut::new_at(&mut data, 0).write_u8(0x41)?;
+        assert_eq!(b"\x41\x00\x00\x00".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u16(0x1234, Endian::Big)?;
+        assert_eq!(b"\x12\x34\x00\x00".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u16(0x1234, Endian::Little)?;
+        assert_eq!(b"\x34\x12\x00\x00".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u32(0x01020304, Endian::Big)?;
+        assert_eq!(b"\x01\x02\x03\x04".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u32(0x01020304, Endian::Little)?;
+        assert_eq!(b"\x04\x03\x02\x01".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u64(0x0102030405060708, Endian::Big)?;
+        assert_eq!(b"\x01\x02\x03\x04\x05\x06\x07\x08".to_vec(), data[0..8].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u64(0x0102030405060708, Endian::Little)?;
+        assert_eq!(b"\x08\x07\x06\x05\x04\x03\x02\x01".to_vec(), data[0..8].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_u128(0x00112233445566778899aabbccddeeff, Endian::Big)?;
+        assert_eq!(b"\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 0).write_u128(0x00112233445566778899aabbccddeeff, Endian::Little)?;
+        assert_eq!(b"\xff\xee\xdd\xcc\xbb\xaa\x99\x88\x77\x66\x55\x44\x33\x22\x11\x00".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_signed() -> SimpleResult<()> {
+        let mut data = vec![0u8; 16];
+
+        ContextMut::new_at(&mut data, 0).write_i8(-1)?;
+        assert_eq!(b"\xff\x00".to_vec(), data[0..2].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_i16(-2, Endian::Big)?;
+        assert_eq!(b"\xff\xfe".to_vec(), data[0..2].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_i16(-2, Endian::Little)?;
+        assert_eq!(b"\xfe\xff".to_vec(), data[0..2].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_i32(-2147483648, Endian::Big)?;
+        assert_eq!(b"\x80\x00\x00\x00".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_i64(-1, Endian::Little)?;
+        assert_eq!(b"\xff\xff\xff\xff\xff\xff\xff\xff".to_vec(), data[0..8].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_i128(1, Endian::Little)?;
+        assert_eq!(b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_float() -> SimpleResult<()> {
+        let mut data = vec![0u8; 8];
+
+        ContextMut::new_at(&mut data, 0).write_f32(25.0, Endian::Big)?;
+        assert_eq!(b"\x41\xc8\x00\x00".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new_at(&mut data, 0).write_f64(3.15, Endian::Big)?;
+        assert_eq!(b"\x40\x09\x33\x33\x33\x33\x33\x33".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 0).write_f64(3.15, Endian::Little)?;
+        assert_eq!(b"\x33\x33\x33\x33\x33\x33\x09\x40".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_half() -> SimpleResult<()> {
+        let mut data = vec![0u8; 2];
+
+        ContextMut::new_at(&mut data, 0).write_f16(f16::from_f32(1.5), Endian::Big)?;
+        assert_eq!(b"\x3e\x00".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 0).write_bf16(bf16::from_f32(1.5), Endian::Little)?;
+        assert_eq!(b"\xc0\x3f".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_back() -> SimpleResult<()> {
+        let mut data = b"AAAABBBBCCCC".to_vec();
+
+        let mut c = ContextMut::new(&mut data).at(4);
+        c.write_u32(0x01020304, Endian::Little)?;
+
+        assert_eq!(4, c.position());
+        assert_eq!(0x01020304, c.as_context().read_u32(Endian::Little)?);
+        assert_eq!(b"AAAA\x04\x03\x02\x01CCCC".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_bytes() -> SimpleResult<()> {
+        let mut data = b"ABCD".to_vec();
+
+        ContextMut::new_at(&mut data, 1).write_bytes(b"XY")?;
+        assert_eq!(b"AXYD".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 4).write_bytes(b"")?;
+        assert_eq!(b"AXYD".to_vec(), data);
+
+        // Arrays and sub-slices work too, and can't write past their end
+        let mut array = *b"ABCD";
+        ContextMut::new(&mut array[1..3]).write_u16(0x5859, Endian::Big)?;
+        assert!(ContextMut::new_at(&mut array[1..3], 1).write_u16(0, Endian::Big).is_err());
+        assert_eq!(*b"AXYD", array);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_past_end() -> SimpleResult<()> {
+        let mut data = b"ABCD".to_vec();
+
+        // None of these should touch the buffer
+        assert!(ContextMut::new_at(&mut data, 4).write_u8(0).is_err());
+        assert!(ContextMut::new_at(&mut data, 3).write_u16(0, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 1).write_u32(0, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 0).write_u64(0, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 0).write_f64(0.0, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 2).write_bytes(b"XYZ").is_err());
+        assert!(ContextMut::new_at(&mut data, u64::MAX).write_u8(0).is_err());
+
+        assert_eq!(b"ABCD".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_odd_widths() -> SimpleResult<()> {
+        let mut data = vec![0u8; 6];
+
+        ContextMut::new_at(&mut data, 0).write_u24(0x010203, Endian::Big)?;
+        assert_eq!(b"\x01\x02\x03\x00\x00\x00".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 3).write_i24(-2, Endian::Little)?;
+        assert_eq!(b"\x01\x02\x03\xfe\xff\xff".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 0).write_uint(0x0102030405, 5, Endian::Little)?;
+        assert_eq!(b"\x05\x04\x03\x02\x01\xff".to_vec(), data);
+
+        ContextMut::new_at(&mut data, 0).write_int(-1, 6, Endian::Big)?;
+        assert_eq!(b"\xff\xff\xff\xff\xff\xff".to_vec(), data);
+
+        // Values that don't fit, and bad widths, don't touch the buffer
+        assert!(ContextMut::new_at(&mut data, 0).write_u24(0x1000000, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 0).write_i24(0x800000, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 0).write_i24(-0x800001, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 0).write_uint(0, 0, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 0).write_int(0, 17, Endian::Big).is_err());
+        assert!(ContextMut::new_at(&mut data, 4).write_u24(0, Endian::Big).is_err());
+        assert_eq!(b"\xff\xff\xff\xff\xff\xff".to_vec(), data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_word_swapped() -> SimpleResult<()> {
+        let mut data = vec![0u8; 8];
+
+        ContextMut::new(&mut data).write_u32(0x12345678, Endian::BigWordSwapped)?;
+        assert_eq!(b"\x56\x78\x12\x34".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new(&mut data).write_u32(0x12345678, Endian::LittleWordSwapped)?;
+        assert_eq!(b"\x34\x12\x78\x56".to_vec(), data[0..4].to_vec());
+
+        ContextMut::new(&mut data).write_f64(1.0, Endian::LittleWordSwapped)?;
+        assert_eq!(b"\x00\x00\xf0\x3f\x00\x00\x00\x00".to_vec(), data);
+
+        ContextMut::new(&mut data).write_int(-2, 6, Endian::BigWordSwapped)?;
+        assert_eq!(b"\xff\xff\xfe\xff\xff\xff\x00\x00".to_vec(), data);
+
+        // Odd sizes can't be swapped, and don't touch the buffer
+        assert!(ContextMut::new(&mut data).write_u24(1, Endian::BigWordSwapped).is_err());
+        assert!(ContextMut::new(&mut data).write_uint(1, 5, Endian::LittleWordSwapped).is_err());
+        assert_eq!(b"\xff\xff\xfe\xff\xff\xff\x00\x00".to_vec(), data);
+
+        // Everything reads back the same
+        for endian in [Endian::BigWordSwapped, Endian::LittleWordSwapped] {
+            ContextMut::new(&mut data).write_i64(-123456789, endian)?;
+            assert_eq!(-123456789, Context::new(&data).read_i64(endian)?);
+
+            ContextMut::new(&mut data).write_f32(3.25, endian)?;
+            assert_eq!(3.25, Context::new(&data).read_f32(endian)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_varints() -> SimpleResult<()> {
+        let mut data = vec![0u8; 10];
+
+        assert_eq!(3, ContextMut::new_at(&mut data, 0).write_uleb128(624485)?);
+        assert_eq!(b"\xe5\x8e\x26".to_vec(), data[0..3].to_vec());
+
+        assert_eq!(3, ContextMut::new_at(&mut data, 0).write_sleb128(-123456)?);
+        assert_eq!(b"\xc0\xbb\x78".to_vec(), data[0..3].to_vec());
+
+        assert_eq!(2, ContextMut::new_at(&mut data, 0).write_sleb128(64)?);
+        assert_eq!(b"\xc0\x00".to_vec(), data[0..2].to_vec());
+
+        assert_eq!(5, ContextMut::new_at(&mut data, 0).write_zigzag(-2147483648)?);
+        assert_eq!(b"\xff\xff\xff\xff\x0f".to_vec(), data[0..5].to_vec());
+
+        assert_eq!(3, ContextMut::new_at(&mut data, 0).write_git_varint(16512)?);
+        assert_eq!(b"\x80\x80\x00".to_vec(), data[0..3].to_vec());
+
+        // Everything reads back the same
+        for value in [0, 1, 63, 64, 127, 128, 16511, 16512, i64::MAX as u64, u64::MAX] {
+            let size = ContextMut::new(&mut data).write_uleb128(value)?;
+            assert_eq!((size, value), Context::new(&data).read_uleb128()?);
+
+            let size = ContextMut::new(&mut data).write_git_varint(value)?;
+            assert_eq!((size, value), Context::new(&data).read_git_varint()?);
+
+            let size = ContextMut::new(&mut data).write_sleb128(value as i64)?;
+            assert_eq!((size, value as i64), Context::new(&data).read_sleb128()?);
+
+            let size = ContextMut::new(&mut data).write_zigzag(value as i64)?;
+            assert_eq!((size, value as i64), Context::new(&data).read_zigzag()?);
+        }
+
+        // Doesn't fit
+        assert!(ContextMut::new_at(&mut data, 8).write_uleb128(624485).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use simple_error::SimpleError;
+use std::fmt;
+use std::io;
+
+use crate::SizedDisplay;
+
+/// A [`Result`] with a [`SizedError`].
+pub type SizedResult<T> = Result<T, SizedError>;
+
+/// Everything that can go wrong while reading, writing, displaying, or
+/// parsing values.
+///
+/// Positions are relative to the [`crate::Context`] (or
+/// [`crate::ContextMut`]) that was used.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SizedError {
+    /// A read or write needed more bytes than the buffer has
+    OutOfBounds {
+        /// Where the read or write started
+        position: u64,
+
+        /// How many bytes it needed
+        needed: u64,
+
+        /// How many bytes there are after `position`
+        available: u64,
+    },
+
+    /// The bytes aren't a valid UTF-8 character
+    InvalidUtf8 {
+        position: u64,
+        bytes: Vec<u8>,
+    },
+
+    /// The words aren't a valid UTF-16 character
+    InvalidUtf16 {
+        position: u64,
+        words: Vec<u16>,
+    },
+
+    /// The value isn't a valid UTF-32 character
+    InvalidUtf32 {
+        position: u64,
+        value: u32,
+    },
+
+    /// The bytes are a variable-length integer that's too long, or doesn't
+    /// fit in 64 bits
+    InvalidVarint {
+        position: u64,
+        bytes: Vec<u8>,
+    },
+
+    /// The type can't be displayed (or parsed) that way, like an integer as
+    /// float parts
+    UnsupportedDisplay {
+        /// The name of the type
+        definition: String,
+        display: SizedDisplay,
+    },
+
+    /// A value can't be converted to another type without losing something
+    Conversion {
+        /// The name of the value's type
+        from: String,
+        to: &'static str,
+    },
+
+    /// A value doesn't fit in the number of bits it's being written to
+    Overflow {
+        value: String,
+        bits: u32,
+    },
+
+    /// A string couldn't be parsed
+    Parse {
+        input: String,
+        reason: String,
+    },
+
+    /// The position doesn't have an address (see
+    /// [`crate::Context::address`])
+    UnmappedPosition {
+        position: u64,
+    },
+
+    /// The address isn't mapped to the buffer (see
+    /// [`crate::Context::at_address`])
+    UnmappedAddress {
+        address: u64,
+    },
+
+    /// A definition or argument doesn't make sense, like a 17-byte integer
+    InvalidArgument(String),
+
+    /// An error from an underlying reader or file
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
+
+    /// The [`fmt::Write`] that a value was being written to failed
+    Format,
+}
+
+impl SizedError {
+    /// An internal function to create a [`SizedError::Parse`].
+    pub(crate) fn parse(input: &str, reason: impl Into<String>) -> Self {
+        Self::Parse {
+            input: input.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// An internal function to move the position of an error, for errors
+    /// that came from reading a copy of part of a buffer.
+    pub(crate) fn offset_by(self, offset: u64) -> Self {
+        match self {
+            Self::OutOfBounds { position, needed, available } => Self::OutOfBounds { position: position + offset, needed, available },
+            Self::InvalidUtf8 { position, bytes }  => Self::InvalidUtf8 { position: position + offset, bytes },
+            Self::InvalidUtf16 { position, words } => Self::InvalidUtf16 { position: position + offset, words },
+            Self::InvalidUtf32 { position, value } => Self::InvalidUtf32 { position: position + offset, value },
+            Self::InvalidVarint { position, bytes } => Self::InvalidVarint { position: position + offset, bytes },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for SizedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { position, needed, available } => {
+                write!(f, "Out of bounds: {} bytes needed at position {}, but only {} available", needed, position, available)
+            },
+            Self::InvalidUtf8 { position, bytes }  => write!(f, "Couldn't find a valid UTF-8 character at position {}: {:02x?}", position, bytes),
+            Self::InvalidUtf16 { position, words } => write!(f, "Couldn't find a valid UTF-16 character at position {}: {:04x?}", position, words),
+            Self::InvalidUtf32 { position, value } => write!(f, "Couldn't find a valid UTF-32 character at position {}: {:#x}", position, value),
+            Self::InvalidVarint { position, bytes } => {
+                write!(f, "Variable-length integer at position {} is too long or doesn't fit in 64 bits: {:02x?}", position, bytes)
+            },
+            Self::UnsupportedDisplay { definition, display } => write!(f, "{} can't be displayed as {:?}", definition, display),
+            Self::Conversion { from, to } => write!(f, "Can't convert {} into {}", from, to),
+            Self::Overflow { value, bits } => write!(f, "Value doesn't fit in {} bits: {}", bits, value),
+            Self::Parse { input, reason } => write!(f, "{}: {}", reason, input),
+            Self::UnmappedPosition { position } => write!(f, "Position isn't mapped to an address: {}", position),
+            Self::UnmappedAddress { address } => write!(f, "Address isn't mapped: {:#x}", address),
+            Self::InvalidArgument(reason) => write!(f, "{}", reason),
+            Self::Io { message, .. } => write!(f, "I/O error: {}", message),
+            Self::Format => write!(f, "Couldn't write the formatted value"),
+        }
+    }
+}
+
+impl std::error::Error for SizedError {}
+
+impl From<io::Error> for SizedError {
+    fn from(e: io::Error) -> Self {
+        Self::Io {
+            kind: e.kind(),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<fmt::Error> for SizedError {
+    fn from(_: fmt::Error) -> Self {
+        Self::Format
+    }
+}
+
+/// Lets code that uses [`simple_error`] keep using `?`.
+impl From<SizedError> for SimpleError {
+    fn from(e: SizedError) -> Self {
+        SimpleError::from(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::convert::TryFrom;
+
+    use crate::{Context, ContextMut, Endian, SizedDefinition, SizedValue};
+
+    #[test]
+    fn test_errors() {
+        let data = b"\xff\xfe\x00\x00\x00\x11\x00\x00\x80".to_vec();
+        let context = Context::new(&data);
+
+        assert_eq!(Err(SizedError::OutOfBounds { position: 6, needed: 4, available: 3 }), context.at(6).read_u32(Endian::Big));
+        assert_eq!(Err(SizedError::OutOfBounds { position: 8, needed: 2, available: 1 }), context.at(8).read_uleb128());
+        assert_eq!(Some(SizedError::OutOfBounds { position: 4, needed: 8, available: 5 }), context.window(4, 8).err());
+        assert_eq!(Err(SizedError::InvalidUtf8 { position: 0, bytes: vec![0xff, 0xfe, 0x00, 0x00] }), context.read_utf8());
+        assert_eq!(Err(SizedError::InvalidUtf32 { position: 4, value: 0x00110000 }), context.at(4).read_utf32(Endian::Big));
+        assert_eq!(Err(SizedError::InvalidVarint { position: 0, bytes: vec![0x80; 10] }), Context::new(&[0x80; 11]).read_uleb128());
+        assert_eq!(Some(SizedError::UnmappedAddress { address: 0x100 }), context.with_base_address(0x200).at_address(0x100).err());
+
+        assert_eq!(
+            Err(SizedError::UnsupportedDisplay { definition: "u32".to_string(), display: SizedDisplay::FloatParts }),
+            SizedDefinition::U32(Endian::Big).to_string(context, SizedDisplay::FloatParts),
+        );
+        assert_eq!(Err(SizedError::Conversion { from: "i8".to_string(), to: "u64" }), u64::try_from(SizedValue::I8(-1)));
+        assert_eq!(Err(SizedError::Overflow { value: "256".to_string(), bits: 8 }), SizedDefinition::U8.from_string("256", SizedDisplay::Decimal));
+        assert_eq!(Err(SizedError::parse("u33le", "Unknown type")), "u33le".parse::<SizedDefinition>());
+
+        let mut buffer = vec![0; 2];
+        assert_eq!(Err(SizedError::OutOfBounds { position: 1, needed: 2, available: 1 }), ContextMut::new_at(&mut buffer, 1).write_u16(1, Endian::Big));
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!("Out of bounds: 4 bytes needed at position 6, but only 3 available", SizedError::OutOfBounds { position: 6, needed: 4, available: 3 }.to_string());
+        assert_eq!("Can't convert i8 into u64", SizedError::Conversion { from: "i8".to_string(), to: "u64" }.to_string());
+        assert_eq!("Unknown type: u33le", SizedError::parse("u33le", "Unknown type").to_string());
+
+        let e: SimpleError = SizedError::UnmappedAddress { address: 0x100 }.into();
+        assert_eq!("Address isn't mapped: 0x100", e.as_str());
+    }
+}
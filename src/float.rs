@@ -0,0 +1,873 @@
+//! Software implementations of floating point formats that Rust doesn't have
+//! natively - x87 80-bit extended precision, and IEEE-754 binary128.
+//!
+//! These are only for reading, writing, and displaying values; there's no
+//! arithmetic. Conversions to and from decimal are done exactly, using a tiny
+//! big integer type, so every value displays with the shortest string of
+//! digits that parses back to the same value.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{SizedError, SizedResult};
+
+#[cfg(feature = "serialize")]
+use serde::{Serialize, Deserialize};
+
+/// An internal description of how a binary float is laid out.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FloatFormat {
+    /// The number of exponent bits.
+    pub exponent_bits: u32,
+
+    /// The number of stored fraction bits, not counting the integer bit.
+    pub mantissa_bits: u32,
+
+    /// Whether the integer bit is stored (like x87 extended precision) or
+    /// implied by the exponent (like everything in IEEE-754).
+    pub explicit_integer: bool,
+}
+
+impl FloatFormat {
+    pub const F16:  Self = Self { exponent_bits: 5,  mantissa_bits: 10,  explicit_integer: false };
+    pub const BF16: Self = Self { exponent_bits: 8,  mantissa_bits: 7,   explicit_integer: false };
+    pub const F32:  Self = Self { exponent_bits: 8,  mantissa_bits: 23,  explicit_integer: false };
+    pub const F64:  Self = Self { exponent_bits: 11, mantissa_bits: 52,  explicit_integer: false };
+    pub const F80:  Self = Self { exponent_bits: 15, mantissa_bits: 63,  explicit_integer: true };
+    pub const F128: Self = Self { exponent_bits: 15, mantissa_bits: 112, explicit_integer: false };
+
+    /// The total width of the float, in bits.
+    pub fn width(self) -> u32 {
+        1 + self.exponent_bits + self.explicit_integer as u32 + self.mantissa_bits
+    }
+
+    pub fn bias(self) -> i64 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    /// The exponent field used for infinity and NaN.
+    pub fn max_exponent(self) -> u128 {
+        (1 << self.exponent_bits) - 1
+    }
+
+    /// Split raw bits into the sign, exponent, integer bit, and mantissa
+    /// fields. For formats without an explicit integer bit, it's implied by
+    /// the exponent.
+    pub fn fields(self, bits: u128) -> (bool, u128, bool, u128) {
+        let mantissa = bits & ((1 << self.mantissa_bits) - 1);
+        let (exponent, integer) = match self.explicit_integer {
+            true  => (bits >> (self.mantissa_bits + 1), (bits >> self.mantissa_bits) & 1 == 1),
+            false => (bits >> self.mantissa_bits, (bits >> self.mantissa_bits) & self.max_exponent() != 0),
+        };
+        let negative = (exponent >> self.exponent_bits) & 1 == 1;
+
+        (negative, exponent & self.max_exponent(), integer, mantissa)
+    }
+
+    /// The inverse of [`FloatFormat::fields`]. The integer bit is ignored
+    /// unless it's explicit.
+    pub fn assemble(self, negative: bool, exponent: u128, integer: bool, mantissa: u128) -> u128 {
+        let sign = (negative as u128) << (self.width() - 1);
+
+        match self.explicit_integer {
+            true  => sign | (exponent << (self.mantissa_bits + 1)) | ((integer as u128) << self.mantissa_bits) | mantissa,
+            false => sign | (exponent << self.mantissa_bits) | mantissa,
+        }
+    }
+
+    /// The number of significant bits, including the integer bit.
+    fn precision(self) -> u32 {
+        self.mantissa_bits + 1
+    }
+
+    /// The power of two that the smallest (subnormal) significand is
+    /// multiplied by.
+    fn min_exponent(self) -> i64 {
+        1 - self.bias() - self.mantissa_bits as i64
+    }
+
+    /// The power of two that the largest significand is multiplied by.
+    fn max_finite_exponent(self) -> i64 {
+        self.max_exponent() as i64 - 1 - self.bias() - self.mantissa_bits as i64
+    }
+
+    /// Decode raw bits into a sign, significand, and power of two.
+    ///
+    /// The x87 oddities fall out naturally: pseudo-denormals (a set integer
+    /// bit with a zero exponent) use the minimum exponent just like
+    /// denormals do, and unnormals (a clear integer bit with a normal
+    /// exponent) are simply a smaller significand. Pseudo-infinities and
+    /// pseudo-NaNs are treated as NaN, which is what the 80387 and later do.
+    pub fn decode(self, bits: u128) -> Decoded {
+        let (negative, exponent, integer, mantissa) = self.fields(bits);
+
+        if exponent == self.max_exponent() {
+            return match (integer, mantissa) {
+                (true, 0) => Decoded::Infinite(negative),
+                _         => Decoded::NaN,
+            };
+        }
+
+        Decoded::Finite(
+            negative,
+            ((integer as u128) << self.mantissa_bits) | mantissa,
+            exponent.max(1) as i64 - self.bias() - self.mantissa_bits as i64,
+        )
+    }
+
+    /// Encode a decoded value back into raw bits. Finite values must already
+    /// be rounded to fit (which [`round_decimal`] does).
+    fn encode(self, value: Decoded) -> u128 {
+        match value {
+            Decoded::NaN => self.assemble(false, self.max_exponent(), true, 1 << (self.mantissa_bits - 1)),
+            Decoded::Infinite(negative) => self.assemble(negative, self.max_exponent(), true, 0),
+            Decoded::Finite(negative, f, e) => {
+                let integer = f >> self.mantissa_bits == 1;
+                let exponent = match integer {
+                    true  => (e + self.bias() + self.mantissa_bits as i64) as u128,
+                    false => 0,
+                };
+
+                self.assemble(negative, exponent, integer, f & ((1 << self.mantissa_bits) - 1))
+            },
+        }
+    }
+}
+
+/// An internal representation of a decoded float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Decoded {
+    NaN,
+
+    /// Infinity, and whether it's negative.
+    Infinite(bool),
+
+    /// Whether it's negative, and a significand and power of two
+    /// (`f × 2^e`). Zero is simply a zero significand.
+    Finite(bool, u128, i64),
+}
+
+/// Compare two decoded values exactly. Zeroes are equal no matter their
+/// sign, and NaN isn't comparable to anything.
+pub(crate) fn compare(a: Decoded, b: Decoded) -> Option<Ordering> {
+    // Put infinity and zero on the same footing as everything else
+    let sign = |d: Decoded| match d {
+        Decoded::NaN                 => None,
+        Decoded::Finite(_, 0, _)     => Some(0),
+        Decoded::Finite(true, _, _)  | Decoded::Infinite(true)  => Some(-1),
+        Decoded::Finite(false, _, _) | Decoded::Infinite(false) => Some(1),
+    };
+
+    let (sign_a, sign_b) = (sign(a)?, sign(b)?);
+    if sign_a != sign_b || sign_a == 0 {
+        return Some(sign_a.cmp(&sign_b));
+    }
+
+    let magnitude = match (a, b) {
+        (Decoded::Infinite(_), Decoded::Infinite(_)) => Ordering::Equal,
+        (Decoded::Infinite(_), _) => Ordering::Greater,
+        (_, Decoded::Infinite(_)) => Ordering::Less,
+        (Decoded::Finite(_, fa, ea), Decoded::Finite(_, fb, eb)) => {
+            // Compare where the top bits are, then line them up - once the
+            // top bits match, neither shift can overflow
+            let top_a = ea + (128 - fa.leading_zeros()) as i64;
+            let top_b = eb + (128 - fb.leading_zeros()) as i64;
+
+            match (top_a.cmp(&top_b), ea.cmp(&eb)) {
+                (Ordering::Equal, Ordering::Greater) => (fa << (ea - eb)).cmp(&fb),
+                (Ordering::Equal, _)                 => fa.cmp(&(fb << (eb - ea))),
+                (o, _) => o,
+            }
+        },
+        _ => return None,
+    };
+
+    match sign_a {
+        1 => Some(magnitude),
+        _ => Some(magnitude.reverse()),
+    }
+}
+
+/// An internal, minimal, arbitrary-precision unsigned integer - just enough
+/// to convert between binary and decimal without losing anything.
+///
+/// The limbs are little endian, with no trailing zeroes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn from_u128(mut v: u128) -> Self {
+        let mut limbs = Vec::new();
+        while v != 0 {
+            limbs.push(v as u32);
+            v >>= 32;
+        }
+
+        Self { limbs }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn bit_len(&self) -> i64 {
+        match self.limbs.last() {
+            Some(last) => (self.limbs.len() as i64 - 1) * 32 + (32 - last.leading_zeros()) as i64,
+            None       => 0,
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn shl(&mut self, n: u32) {
+        if self.is_zero() {
+            return;
+        }
+
+        let bits = n % 32;
+        if bits != 0 {
+            let mut carry = 0;
+            for limb in self.limbs.iter_mut() {
+                let v = ((*limb as u64) << bits) | carry;
+                *limb = v as u32;
+                carry = v >> 32;
+            }
+            if carry != 0 {
+                self.limbs.push(carry as u32);
+            }
+        }
+
+        self.limbs.splice(0..0, vec![0; (n / 32) as usize]);
+    }
+
+    fn shr1(&mut self) {
+        let mut carry = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let next = *limb & 1;
+            *limb = (*limb >> 1) | (carry << 31);
+            carry = next;
+        }
+        self.trim();
+    }
+
+    fn mul_small(&mut self, m: u32) {
+        let mut carry = 0;
+        for limb in self.limbs.iter_mut() {
+            let v = *limb as u64 * m as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.trim();
+    }
+
+    fn mul_pow10(&mut self, n: u32) {
+        for _ in 0..(n / 9) {
+            self.mul_small(1_000_000_000);
+        }
+        self.mul_small(10u32.pow(n % 9));
+    }
+
+    fn add(&mut self, other: &Self) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let v = *limb as u64 + *other.limbs.get(i).unwrap_or(&0) as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// Subtract `other`, which must not be bigger than `self`.
+    fn sub(&mut self, other: &Self) {
+        let mut borrow = false;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let (v, b1) = limb.overflowing_sub(*other.limbs.get(i).unwrap_or(&0));
+            let (v, b2) = v.overflowing_sub(borrow as u32);
+            *limb = v;
+            borrow = b1 || b2;
+        }
+        self.trim();
+    }
+
+    /// Divide by `other`, for quotients that fit in a [`u128`]. Returns the
+    /// quotient, and whether there was a remainder.
+    fn div(mut self, other: &Self) -> (u128, bool) {
+        let shift = (self.bit_len() - other.bit_len()).max(0);
+        let mut divisor = other.clone();
+        divisor.shl(shift as u32);
+
+        let mut quotient = 0;
+        for _ in 0..=shift {
+            quotient <<= 1;
+            if self >= divisor {
+                self.sub(&divisor);
+                quotient |= 1;
+            }
+            divisor.shr1();
+        }
+
+        (quotient, !self.is_zero())
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// An internal function to find the shortest decimal digits that uniquely
+/// identify `f × 2^e` (which must not be zero) in the given format.
+///
+/// Returns the digits and a power of ten `k`, where the value is
+/// `0.d1d2d3... × 10^k`. This is the free-format algorithm from Burger and
+/// Dybvig's "Printing Floating-Point Numbers Quickly and Accurately", using
+/// round-half-even for the boundaries, the same as Rust's own floats.
+fn shortest_digits(f: u128, e: i64, format: FloatFormat) -> (Vec<u8>, i64) {
+    // The gap to the next value down is half as big at a power of two
+    let lower_closer = f == 1 << (format.precision() - 1) && e > format.min_exponent();
+    let inclusive = f & 1 == 0;
+
+    // Scale everything so the value is r / s, and the gaps to the neighbouring
+    // values are m_plus / s and m_minus / s (all doubled, to avoid fractions)
+    let mut r = BigUint::from_u128(f);
+    let mut s = BigUint::from_u128(1);
+    let mut m_plus = BigUint::from_u128(1);
+    let mut m_minus = BigUint::from_u128(1);
+
+    let extra = lower_closer as u32;
+    if e >= 0 {
+        r.shl(e as u32 + 1 + extra);
+        s.shl(1 + extra);
+        m_plus.shl(e as u32 + extra);
+        m_minus.shl(e as u32);
+    } else {
+        r.shl(1 + extra);
+        s.shl((-e) as u32 + 1 + extra);
+        m_plus.shl(extra);
+    }
+
+    // Estimate the power of ten, then fix it up
+    let mut k = ((f as f64).log10() + e as f64 * std::f64::consts::LOG10_2 - 1e-10).ceil() as i64;
+    if k >= 0 {
+        s.mul_pow10(k as u32);
+    } else {
+        r.mul_pow10((-k) as u32);
+        m_plus.mul_pow10((-k) as u32);
+        m_minus.mul_pow10((-k) as u32);
+    }
+
+    let past_high = |r: &BigUint, m_plus: &BigUint, s: &BigUint| {
+        let mut high = r.clone();
+        high.add(m_plus);
+
+        match inclusive {
+            true  => high >= *s,
+            false => high > *s,
+        }
+    };
+
+    while past_high(&r, &m_plus, &s) {
+        s.mul_small(10);
+        k += 1;
+    }
+
+    loop {
+        let mut r10 = r.clone();
+        r10.mul_small(10);
+        let mut m_plus10 = m_plus.clone();
+        m_plus10.mul_small(10);
+
+        if past_high(&r10, &m_plus10, &s) {
+            break;
+        }
+
+        r = r10;
+        m_plus = m_plus10;
+        m_minus.mul_small(10);
+        k -= 1;
+    }
+
+    // Generate digits until the value is within the gap on either side
+    let mut digits = Vec::new();
+    loop {
+        r.mul_small(10);
+        m_plus.mul_small(10);
+        m_minus.mul_small(10);
+
+        let mut digit = 0;
+        while r >= s {
+            r.sub(&s);
+            digit += 1;
+        }
+
+        let low = match inclusive {
+            true  => r <= m_minus,
+            false => r < m_minus,
+        };
+        let high = past_high(&r, &m_plus, &s);
+
+        match (low, high) {
+            (false, false) => digits.push(digit),
+            (true,  false) => { digits.push(digit); break; },
+            (false, true)  => { digits.push(digit + 1); break; },
+            (true,  true)  => {
+                let mut r2 = r.clone();
+                r2.shl(1);
+                digits.push(if r2 < s { digit } else { digit + 1 });
+                break;
+            },
+        }
+    }
+
+    (digits, k)
+}
+
+/// An internal function to display a float's raw bits in decimal, the same
+/// way Rust displays its own floats - either positionally (`{}`) or in
+/// scientific notation (`{:e}`).
+fn format_decimal(bits: u128, format: FloatFormat, scientific: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let (negative, significand, exponent) = match format.decode(bits) {
+        Decoded::NaN                  => return write!(f, "NaN"),
+        Decoded::Infinite(negative)   => return write!(f, "{}inf", if negative { "-" } else { "" }),
+        Decoded::Finite(negative, significand, exponent) => (negative, significand, exponent),
+    };
+
+    if negative {
+        write!(f, "-")?;
+    }
+
+    if significand == 0 {
+        return write!(f, "{}", if scientific { "0e0" } else { "0" });
+    }
+
+    let (digits, k) = shortest_digits(significand, exponent, format);
+    let digits: String = digits.iter().map(|d| (b'0' + d) as char).collect();
+
+    if scientific {
+        return match digits.len() {
+            1 => write!(f, "{}e{}", digits, k - 1),
+            _ => write!(f, "{}.{}e{}", &digits[..1], &digits[1..], k - 1),
+        };
+    }
+
+    if k <= 0 {
+        write!(f, "0.{}{}", "0".repeat((-k) as usize), digits)
+    } else if k as usize >= digits.len() {
+        write!(f, "{}{}", digits, "0".repeat(k as usize - digits.len()))
+    } else {
+        write!(f, "{}.{}", &digits[..(k as usize)], &digits[(k as usize)..])
+    }
+}
+
+/// An internal function to parse a decimal number (positional or scientific,
+/// just like [`f64::from_str`] accepts) into raw bits, rounding correctly
+/// (half to even).
+fn parse_decimal(s: &str, format: FloatFormat) -> SizedResult<u128> {
+    let (negative, unsigned) = match (s.strip_prefix('-'), s.strip_prefix('+')) {
+        (Some(s), _) => (true, s),
+        (_, Some(s)) => (false, s),
+        _            => (false, s),
+    };
+
+    match unsigned.to_lowercase().as_str() {
+        "inf" | "infinity" => return Ok(format.encode(Decoded::Infinite(negative))),
+        "nan"              => return Ok(format.encode(Decoded::NaN)),
+        _                  => (),
+    };
+
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(i) => (&unsigned[..i], &unsigned[(i + 1)..]),
+        None    => (unsigned, "0"),
+    };
+    let (whole, fraction) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], &mantissa[(i + 1)..]),
+        None    => (mantissa, ""),
+    };
+
+    if whole.len() + fraction.len() == 0 || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(SizedError::parse(s, "Not a valid floating point value"));
+    }
+    let exponent: i64 = match exponent.parse() {
+        Ok(e)  => e,
+        Err(_) => return Err(SizedError::parse(s, "Not a valid floating point value")),
+    };
+
+    // Collect the significant digits as an integer, and shift the exponent to
+    // match
+    let digits = format!("{}{}", whole, fraction);
+    let digits = digits.trim_start_matches('0');
+    let exponent = exponent.saturating_sub(fraction.len() as i64);
+
+    // Nine digits at a time is as much as fits in a limb
+    let mut value = BigUint::from_u128(0);
+    for chunk in digits.as_bytes().chunks(9) {
+        value.mul_pow10(chunk.len() as u32);
+        value.add(&BigUint::from_u128(chunk.iter().fold(0, |acc, d| acc * 10 + (d - b'0') as u128)));
+    }
+
+    // Anything this far out is zero or infinity in every format we have, and
+    // this keeps the big integers to a sane size
+    let magnitude = exponent.saturating_add(digits.len() as i64);
+    let decoded = if value.is_zero() || magnitude < -6000 {
+        Decoded::Finite(negative, 0, 0)
+    } else if magnitude > 6000 {
+        Decoded::Infinite(negative)
+    } else {
+        round_decimal(negative, value, exponent, format)
+    };
+
+    Ok(format.encode(decoded))
+}
+
+/// An internal function to round `value × 10^exponent` to the nearest value
+/// in the given format (half to even).
+fn round_decimal(negative: bool, mut numerator: BigUint, exponent: i64, format: FloatFormat) -> Decoded {
+    let precision = format.precision() as i64;
+    let mut denominator = BigUint::from_u128(1);
+
+    match exponent >= 0 {
+        true  => numerator.mul_pow10(exponent as u32),
+        false => denominator.mul_pow10((-exponent) as u32),
+    };
+
+    // Scale by 2^scale so the quotient has a couple more bits than we need
+    // (for rounding), then the value is (quotient + remainder) × 2^-scale
+    let scale = precision + 2 + denominator.bit_len() - numerator.bit_len();
+    match scale >= 0 {
+        true  => numerator.shl(scale as u32),
+        false => denominator.shl((-scale) as u32),
+    };
+    let (quotient, remainder) = numerator.div(&denominator);
+
+    // Figure out the final exponent, capped at the subnormal exponent, and
+    // shift the quotient to match
+    let leading = 128 - quotient.leading_zeros() as i64 - 1 - scale;
+    let mut e = (leading - (precision - 1)).max(format.min_exponent());
+    let shift = (e + scale) as u32;
+
+    let mut f = quotient.checked_shr(shift).unwrap_or(0);
+    let half = quotient.checked_shr(shift - 1).unwrap_or(0) & 1 == 1;
+    let sticky = remainder || quotient & 1u128.checked_shl(shift - 1).unwrap_or(0).wrapping_sub(1) != 0;
+
+    if half && (sticky || f & 1 == 1) {
+        f += 1;
+        if f == 1 << precision {
+            f >>= 1;
+            e += 1;
+        }
+    }
+
+    match e > format.max_finite_exponent() {
+        true  => Decoded::Infinite(negative),
+        false => Decoded::Finite(negative, f, e),
+    }
+}
+
+/// An internal function to get a float's value as a [`f64`], rounding (and
+/// possibly overflowing to infinity) if it doesn't fit.
+fn to_f64_lossy(bits: u128, format: FloatFormat) -> f64 {
+    match format.decode(bits) {
+        Decoded::NaN => f64::NAN,
+        Decoded::Infinite(false) => f64::INFINITY,
+        Decoded::Infinite(true)  => f64::NEG_INFINITY,
+        Decoded::Finite(negative, f, e) => {
+            let mut v = f as f64;
+
+            // Scale in steps, since a power of two this big won't fit in a f64
+            let mut e = e;
+            while e != 0 {
+                let step = e.clamp(-1000, 1000);
+                v *= 2f64.powi(step as i32);
+                e -= step;
+            }
+
+            if negative { -v } else { v }
+        },
+    }
+}
+
+macro_rules! software_float {
+    ($name:ident, $format:expr, $width:expr, $description:expr) => {
+        #[doc = $description]
+        ///
+        /// This only holds the raw bits - it can be displayed (with the
+        /// shortest decimal representation that round trips, just like Rust's
+        /// own floats), parsed, and converted to a [`f64`], but there's no
+        /// arithmetic.
+        #[derive(Debug, Clone, Copy)]
+        #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+        pub struct $name {
+            bits: u128,
+        }
+
+        impl $name {
+            /// Create a value from its raw bits. Bits past the width of the
+            /// type are ignored.
+            pub fn from_bits(bits: u128) -> Self {
+                Self { bits: bits & (u128::MAX >> (128 - $width)) }
+            }
+
+            /// Get the raw bits.
+            pub fn to_bits(self) -> u128 {
+                self.bits
+            }
+
+            /// Returns `true` for NaN, including the x87 pseudo-NaN and
+            /// pseudo-infinity encodings.
+            pub fn is_nan(self) -> bool {
+                $format.decode(self.bits) == Decoded::NaN
+            }
+
+            /// Convert to the nearest [`f64`]. Values too big or small for a
+            /// [`f64`] become infinity or zero.
+            pub fn to_f64(self) -> f64 {
+                to_f64_lossy(self.bits, $format)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                format_decimal(self.bits, $format, false, f)
+            }
+        }
+
+        impl fmt::LowerExp for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                format_decimal(self.bits, $format, true, f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = SizedError;
+
+            /// Parse a decimal value, in positional or scientific notation,
+            /// to the nearest value.
+            fn from_str(s: &str) -> SizedResult<Self> {
+                Ok(Self::from_bits(parse_decimal(s, $format)?))
+            }
+        }
+    };
+}
+
+software_float!(Float80, FloatFormat::F80, 80,
+    "An x87 80-bit extended precision float (often a `long double`).\n\nUnlike IEEE-754 formats, the integer bit is stored explicitly, which allows for some odd encodings: pseudo-denormals display as the denormal they look like, unnormals display as their (smaller) value, and pseudo-infinities and pseudo-NaNs display as `NaN`."
+);
+
+software_float!(Float128, FloatFormat::F128, 128,
+    "An IEEE-754 binary128 (quad precision) float."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::{SimpleError, SimpleResult};
+
+    #[test]
+    fn test_matches_f64() -> SimpleResult<()> {
+        // Our decimal conversion should agree exactly with Rust's, so run a
+        // bunch of f64 values through both
+        let mut values: Vec<f64> = vec![
+            0.1, 0.3, 1.0, 1.5, 3.15, 100.0, 1e23, 5e-324, 2.2250738585072014e-308,
+            f64::MAX, f64::MIN_POSITIVE, 123456789.0, 9007199254740993.0,
+        ];
+
+        // A simple pseudo-random generator gives us a good spread of bits
+        let mut seed = 0x2545f4914f6cdd1du64;
+        for _ in 0..2000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            values.push(f64::from_bits(seed));
+        }
+
+        for v in values.into_iter().filter(|v| v.is_finite()) {
+            let bits = v.to_bits() as u128;
+            let displayed = format!("{}", Wrapper(bits, FloatFormat::F64));
+
+            assert_eq!(format!("{}", v), displayed);
+            assert_eq!(format!("{:e}", v), format!("{:e}", Wrapper(bits, FloatFormat::F64)));
+            assert_eq!(bits, parse_decimal(&displayed, FloatFormat::F64)?);
+        }
+
+        // Parsing more digits than we need should round correctly
+        let tests = vec![
+            "0.1000000000000000055511151231257827021181583404541015625",
+            "2.4703282292062327208828439643411068618252990130716238221279284125033775363510437593264991818081799618989828234772285886546332835517796989819938739800539093906315035659515570226392290858392449105184435931802849936536152500319370457678249219365623669863658480757001585769269903706311928279558551332927834338409351978015531246597263579574622766465272827220056374006485499977096599470454020828166226237857393450736339007967761930577506740176324673600968951340535537458516661134223766678604162159680461914467291840300530057530849048765391711386591646239524912623653881879636239373280423891018672348497668235089863388587925628302755995657524455507255189313690836254779186948667994968324049705821028513185451396213837722826145437693412532098591327667236328125e-324",
+            "1e400",
+        ];
+        for s in tests {
+            assert_eq!(s.parse::<f64>().unwrap().to_bits() as u128, parse_decimal(s, FloatFormat::F64)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_float80() -> SimpleResult<()> {
+        let tests = vec![
+            // bits                        decimal                    scientific
+            (0x3fff8000000000000000u128,   "1",                       "1e0"),
+            (0xbfffc000000000000000,       "-1.5",                    "-1.5e0"),
+            (0x4000c90fdaa22168c235,       "3.1415926535897932385",   "3.1415926535897932385e0"),
+            (0x3ffbcccccccccccccccd,       "0.1",                     "1e-1"),
+            (0x00000000000000000000,       "0",                       "0e0"),
+            (0x80000000000000000000,       "-0",                      "-0e0"),
+            (0x7fff8000000000000000,       "inf",                     "inf"),
+            (0xffff8000000000000000,       "-inf",                    "-inf"),
+            (0x7fffc000000000000000,       "NaN",                     "NaN"),
+
+            // Pseudo-infinity and pseudo-NaN (no integer bit)
+            (0x7fff0000000000000000,       "NaN",                     "NaN"),
+            (0x7fff4000000000000000,       "NaN",                     "NaN"),
+
+            // Unnormal - 0.1 (binary) × 2^0
+            (0x3fff4000000000000000,       "0.5",                     "5e-1"),
+        ];
+
+        for (bits, decimal, scientific) in tests {
+            let v = Float80::from_bits(bits);
+
+            assert_eq!(decimal, v.to_string());
+            assert_eq!(scientific, format!("{:e}", v));
+        }
+
+        // The very big and very small ones are too long to write out
+        let tests = vec![
+            // bits                        decimal                                                   scientific
+            // Pseudo-denormal is the same value as the smallest normal
+            (0x00008000000000000000u128,   format!("0.{}33621031431120935063", "0".repeat(4931)),    "3.3621031431120935063e-4932"),
+            (0x00018000000000000000,       format!("0.{}33621031431120935063", "0".repeat(4931)),    "3.3621031431120935063e-4932"),
+
+            // Smallest denormal, and largest finite value
+            (0x00000000000000000001,       format!("0.{}4", "0".repeat(4950)),                       "4e-4951"),
+            (0x7ffeffffffffffffffff,       format!("1189731495357231765{}", "0".repeat(4914)),       "1.189731495357231765e4932"),
+        ];
+
+        for (bits, decimal, scientific) in tests {
+            let v = Float80::from_bits(bits);
+
+            assert_eq!(decimal, v.to_string());
+            assert_eq!(scientific, format!("{:e}", v));
+        }
+
+        // Both encodings of the smallest normal are the same value
+        let pseudo_denormal = Float80::from_bits(0x00008000000000000000);
+        let normal = Float80::from_bits(0x00018000000000000000);
+        assert_eq!(normal.to_f64(), pseudo_denormal.to_f64());
+        assert_eq!(crate::SizedValue::F80(normal), crate::SizedValue::F80(pseudo_denormal));
+        assert!(crate::SizedValue::F80(normal) > crate::SizedValue::F80(Float80::from_bits(0x00007fffffffffffffff)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_float128() -> SimpleResult<()> {
+        let tests = vec![
+            // bits                                      decimal                                  scientific
+            (0x3fff0000000000000000000000000000u128,     "1",                                     "1e0"),
+            (0xc0000000000000000000000000000000,         "-2",                                    "-2e0"),
+            (0x3ffb999999999999999999999999999a,         "0.1",                                   "1e-1"),
+            (0x4000921fb54442d18469898cc51701b8,         "3.1415926535897932384626433832795028",  "3.1415926535897932384626433832795028e0"),
+            (0x3ffe0000000000000000000000000000,         "0.5",                                   "5e-1"),
+            (0x80000000000000000000000000000000,         "-0",                                    "-0e0"),
+            (0x7fff0000000000000000000000000000,         "inf",                                   "inf"),
+            (0x7fff8000000000000000000000000000,         "NaN",                                   "NaN"),
+            (0x00000000000000000000000000000001,         "0.",                                    "6e-4966"),
+        ];
+
+        for (bits, decimal, scientific) in tests {
+            let v = Float128::from_bits(bits);
+
+            let displayed = v.to_string();
+            assert!(displayed.starts_with(decimal), "{} doesn't start with {}", displayed, decimal);
+            assert_eq!(scientific, format!("{:e}", v));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> SimpleResult<()> {
+        assert_eq!(0x3ffbcccccccccccccccd, "0.1".parse::<Float80>()?.to_bits());
+        assert_eq!(0x4000c90fdaa22168c235, "3.1415926535897932385".parse::<Float80>()?.to_bits());
+        assert_eq!(0xbfffc000000000000000, "-1.5e0".parse::<Float80>()?.to_bits());
+        assert_eq!(0x00000000000000000001, "4e-4951".parse::<Float80>()?.to_bits());
+        assert_eq!(0x00000000000000000000, "1e-4952".parse::<Float80>()?.to_bits());
+        assert_eq!(0x7fff8000000000000000, "1e5000".parse::<Float80>()?.to_bits());
+        assert_eq!(0x3ffb999999999999999999999999999a, "0.1".parse::<Float128>()?.to_bits());
+        assert_eq!(0x4000921fb54442d18469898cc51701b8, "3.1415926535897932384626433832795028".parse::<Float128>()?.to_bits());
+
+        assert!("".parse::<Float80>().is_err());
+        assert!("1.2.3".parse::<Float80>().is_err());
+        assert!("0x12".parse::<Float128>().is_err());
+
+        // Anything canonical should round trip through both notations
+        let mut seed = 0x9e3779b97f4a7c15u64;
+        for _ in 0..100 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let bits = ((seed as u128) << 64) | (seed.rotate_left(23) ^ 0x5555) as u128;
+
+            for (format, width) in [(FloatFormat::F80, 80), (FloatFormat::F128, 128)] {
+                let bits = bits >> (128 - width);
+                if format.decode(bits) == Decoded::NaN || format.encode(format.decode(bits)) != bits {
+                    continue;
+                }
+
+                let mut positional = String::new();
+                let mut scientific = String::new();
+                fmt::write(&mut positional, format_args!("{}", Wrapper(bits, format)))
+                    .map_err(|e| SimpleError::new(e.to_string()))?;
+                fmt::write(&mut scientific, format_args!("{:e}", Wrapper(bits, format)))
+                    .map_err(|e| SimpleError::new(e.to_string()))?;
+
+                assert_eq!(bits, parse_decimal(&positional, format)?);
+                assert_eq!(bits, parse_decimal(&scientific, format)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A wrapper to display bits of any format through our own conversion.
+    struct Wrapper(u128, FloatFormat);
+
+    impl fmt::Display for Wrapper {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            format_decimal(self.0, self.1, false, f)
+        }
+    }
+
+    impl fmt::LowerExp for Wrapper {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            format_decimal(self.0, self.1, true, f)
+        }
+    }
+}
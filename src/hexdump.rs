@@ -0,0 +1,278 @@
+use std::fmt;
+use std::fmt::Write;
+
+#[cfg(feature = "serialize")]
+use serde::{Serialize, Deserialize};
+
+use crate::{Context, HexOptions, SizedDefinition, SizedDisplay, SizedError, SizedResult};
+
+/// What to show in the panel on the right of a [`hexdump`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum HexdumpText {
+    /// No panel - just the offsets and hex
+    None,
+
+    /// Printable ASCII characters, and `.` for everything else
+    Ascii,
+
+    /// Printable UTF-8 characters, and `.` for everything else. A multi-byte
+    /// character is shown at its first byte, and its other bytes are shown
+    /// as spaces so the columns still line up.
+    Utf8,
+}
+
+/// Configure the layout of a [`hexdump`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct HexdumpOptions {
+    /// Bytes per line - must be a multiple of the size of `group`
+    pub width: usize,
+
+    /// How the bytes are grouped - any fixed-size integer, such as
+    /// [`SizedDefinition::U8`] for bytes or `U16(Endian::Little)` for
+    /// little-endian words
+    pub group: SizedDefinition,
+
+    /// How each group is displayed - unpadded values still line up, but
+    /// are harder to read
+    pub hex: HexOptions,
+
+    /// Start each line with its position, in hex (`00000010`)
+    pub offsets: bool,
+
+    /// The panel on the right
+    pub text: HexdumpText,
+}
+
+impl Default for HexdumpOptions {
+    fn default() -> Self {
+        Self {
+            width: 16,
+            group: SizedDefinition::U8,
+            hex: HexOptions {
+                uppercase: false,
+                prefix: false,
+                padded: true,
+                hex_float: false,
+            },
+            offsets: true,
+            text: HexdumpText::Ascii,
+        }
+    }
+}
+
+/// Counts the characters that go through it, so short lines can be padded.
+struct Counter<'a, W: fmt::Write> {
+    out: &'a mut W,
+    count: usize,
+}
+
+impl<'a, W: fmt::Write> fmt::Write for Counter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.count += s.chars().count();
+        self.out.write_str(s)
+    }
+}
+
+/// Render `len` bytes, starting at the context, in the style of
+/// `hexdump -C`, into a [`String`].
+///
+/// See [`write_hexdump`] for details.
+///
+/// ```
+/// use sized_number::*;
+///
+/// let buffer = b"Hello, world!\n\x00\x01\x02".to_vec();
+/// let context = Context::new(&buffer);
+///
+/// assert_eq!(
+///     "00000000  48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a 00 01  |Hello, world!...|\n\
+///      00000010  02                                               |.|\n",
+///     hexdump(context, 17, &HexdumpOptions::default()).unwrap(),
+/// );
+///
+/// let options = HexdumpOptions {
+///     width: 8,
+///     group: SizedDefinition::U16(Endian::Little),
+///     text: HexdumpText::None,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     "00000000  6548 6c6c 2c6f 7720\n\
+///      00000008  726f 646c 0a21 0100\n",
+///     hexdump(context, 16, &options).unwrap(),
+/// );
+/// ```
+pub fn hexdump(context: Context, len: u64, options: &HexdumpOptions) -> SizedResult<String> {
+    let mut out = String::new();
+    write_hexdump(context, len, options, &mut out)?;
+
+    Ok(out)
+}
+
+/// Render `len` bytes, starting at the context, in the style of
+/// `hexdump -C`.
+///
+/// Every line ends with a newline. The offsets are positions in the
+/// context's buffer. If the last group on the last line is cut short, its
+/// bytes are displayed one at a time.
+///
+/// Fails if the region is out of bounds, or if the options don't make sense
+/// (a group that isn't a fixed-size integer, or a width that isn't a
+/// multiple of it).
+pub fn write_hexdump<W: fmt::Write>(context: Context, len: u64, options: &HexdumpOptions, out: &mut W) -> SizedResult<()> {
+    let group_size = match options.group {
+        SizedDefinition::U8 | SizedDefinition::U16(_) | SizedDefinition::U24(_) | SizedDefinition::U32(_) |
+        SizedDefinition::U64(_) | SizedDefinition::U128(_) | SizedDefinition::UN(_, _) |
+        SizedDefinition::I8 | SizedDefinition::I16(_) | SizedDefinition::I24(_) | SizedDefinition::I32(_) |
+        SizedDefinition::I64(_) | SizedDefinition::I128(_) | SizedDefinition::IN(_, _) => options.group.fixed_size().unwrap_or(0),
+        _ => return Err(SizedError::InvalidArgument(format!("Hexdump groups must be fixed-size integers, not {:?}", options.group))),
+    };
+
+    let width = options.width as u64;
+    if group_size == 0 || width == 0 || !width.is_multiple_of(group_size) {
+        return Err(SizedError::InvalidArgument(format!("Hexdump width ({}) must be a multiple of the group size ({})", width, group_size)));
+    }
+
+    let region = context.window(context.position(), len)?;
+    let groups = width / group_size;
+    let prefix = if options.hex.prefix { 2 } else { 0 };
+    let hex_width = (groups * (group_size * 2 + prefix) + groups - 1) as usize;
+
+    // Bytes of a UTF-8 character that was already shown
+    let mut continuation = 0;
+
+    for line in (0..len).step_by(options.width) {
+        let line_end = std::cmp::min(line + width, len);
+
+        if options.offsets {
+            write!(out, "{:08x}  ", context.position() + line)?;
+        }
+
+        let mut hex = Counter { out: &mut *out, count: 0 };
+        for group in (line..line_end).step_by(group_size as usize) {
+            if group != line {
+                hex.write_char(' ')?;
+            }
+
+            if group + group_size <= line_end {
+                options.group.write_to(region.at(group), SizedDisplay::Hex(options.hex), &mut hex)?;
+            } else {
+                for byte in group..line_end {
+                    if byte != group {
+                        hex.write_char(' ')?;
+                    }
+                    SizedDefinition::U8.write_to(region.at(byte), SizedDisplay::Hex(options.hex), &mut hex)?;
+                }
+            }
+        }
+        let padding = hex_width.saturating_sub(hex.count);
+
+        if options.text != HexdumpText::None {
+            write!(out, "{:padding$}  |", "", padding = padding)?;
+
+            for position in line..line_end {
+                let c = match options.text {
+                    HexdumpText::Utf8 if continuation > 0 => {
+                        continuation -= 1;
+                        ' '
+                    },
+                    HexdumpText::Utf8 => match region.at(position).read_utf8() {
+                        Ok((size, c)) if !c.is_control() => {
+                            continuation = size - 1;
+                            c
+                        },
+                        _ => '.',
+                    },
+                    _ => match region.at(position).read_u8()? {
+                        b @ 0x20..=0x7e => b as char,
+                        _ => '.',
+                    },
+                };
+                out.write_char(c)?;
+            }
+
+            out.write_char('|')?;
+        }
+
+        out.write_char('\n')?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::SimpleResult;
+
+    use crate::Endian;
+
+    #[test]
+    fn test_hexdump() -> SimpleResult<()> {
+        let data = b"\x00ABCDEFG\x7f\xffhi\xc3\xa9!\n".to_vec();
+        let context = Context::new(&data);
+
+        let tests = vec![
+            // len  options                                                                        expected
+            (
+                16, HexdumpOptions::default(),
+                "00000000  00 41 42 43 44 45 46 47 7f ff 68 69 c3 a9 21 0a  |.ABCDEFG..hi..!.|\n",
+            ),
+            (
+                11, HexdumpOptions { width: 4, ..Default::default() },
+                "00000000  00 41 42 43  |.ABC|\n\
+                 00000004  44 45 46 47  |DEFG|\n\
+                 00000008  7f ff 68     |..h|\n",
+            ),
+            (
+                16, HexdumpOptions { width: 8, text: HexdumpText::Utf8, ..Default::default() },
+                "00000000  00 41 42 43 44 45 46 47  |.ABCDEFG|\n\
+                 00000008  7f ff 68 69 c3 a9 21 0a  |..hié !.|\n",
+            ),
+            (
+                14, HexdumpOptions { width: 13, text: HexdumpText::Utf8, ..Default::default() },
+                "00000000  00 41 42 43 44 45 46 47 7f ff 68 69 c3  |.ABCDEFG..hié|\n\
+                 0000000d  a9                                      | |\n",
+            ),
+            (
+                15, HexdumpOptions { width: 8, group: SizedDefinition::U32(Endian::Big), hex: HexOptions { uppercase: true, ..HexdumpOptions::default().hex }, ..Default::default() },
+                "00000000  00414243 44454647  |.ABCDEFG|\n\
+                 00000008  7FFF6869 C3 A9 21  |..hi..!|\n",
+            ),
+            (
+                8, HexdumpOptions { width: 8, group: SizedDefinition::U16(Endian::Little), hex: Default::default(), offsets: false, ..Default::default() },
+                "0x4100 0x4342 0x4544 0x4746  |.ABCDEFG|\n",
+            ),
+            (
+                0, HexdumpOptions::default(),
+                "",
+            ),
+        ];
+
+        for (len, options, expected) in tests {
+            assert_eq!(expected, hexdump(context, len, &options)?);
+        }
+
+        // The offsets are positions in the buffer
+        assert_eq!("0000000c  c3a9  |é |\n", hexdump(context.at(12), 2, &HexdumpOptions { width: 2, group: SizedDefinition::U16(Endian::Big), text: HexdumpText::Utf8, ..Default::default() })?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hexdump_errors() {
+        let data = b"ABCD".to_vec();
+        let context = Context::new(&data);
+
+        assert_eq!(Err(SizedError::OutOfBounds { position: 2, needed: 4, available: 2 }), hexdump(context.at(2), 4, &HexdumpOptions::default()));
+        assert!(hexdump(context, 4, &HexdumpOptions { width: 0, ..Default::default() }).is_err());
+        assert!(hexdump(context, 4, &HexdumpOptions { width: 6, group: SizedDefinition::U32(Endian::Big), ..Default::default() }).is_err());
+        assert!(hexdump(context, 4, &HexdumpOptions { group: SizedDefinition::F32(Endian::Big), ..Default::default() }).is_err());
+        assert!(hexdump(context, 4, &HexdumpOptions { group: SizedDefinition::ULEB128, ..Default::default() }).is_err());
+    }
+}
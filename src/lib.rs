@@ -44,8 +44,8 @@
 //!
 //! In addition to formatting a string, 64-bit and smaller unsigned instances
 //! of [`SizedDefinition`] can be converted into [`u64`] (unsigned) integers, and
-//! 64-bit and smaller signed instances can be converted into [`i64`] (signed)
-//! integers:
+//! 64-bit and smaller signed instances (and anything smaller than 64 bits) can
+//! be converted into [`i64`] (signed) integers:
 //!
 //! ```
 //! use sized_number::{Context, SizedDefinition, Endian, SizedDisplay, HexOptions, BinaryOptions, ScientificOptions};
@@ -59,22 +59,76 @@
 //! assert_eq!(0x01020304,         SizedDefinition::U32(Endian::Big).to_u64(context).unwrap());
 //! assert_eq!(0x0102030405060708, SizedDefinition::U64(Endian::Big).to_u64(context).unwrap());
 //! ```
+//!
+//! Any type can also be read into a [`SizedValue`], which holds the value
+//! itself and can be compared, converted, or formatted later without reading
+//! it again:
+//!
+//! ```
+//! use sized_number::{Context, SizedDefinition, SizedValue, SizedDisplay, Endian};
+//!
+//! let buffer = b"\x80\x00".to_vec();
+//! let context = Context::new_at(&buffer, 0);
+//!
+//! let v = SizedDefinition::I16(Endian::Big).read(context).unwrap();
+//! assert_eq!(SizedValue::I16(-32768), v);
+//! assert!(v < SizedValue::U8(0));
+//! assert_eq!("0x8000", v.format(SizedDisplay::Hex(Default::default())).unwrap());
+//! ```
+//!
+//! Values can also be written back into a buffer using a [`ContextMut`], which
+//! has a `write_*` function to match each `read_*` function on [`Context`]:
+//!
+//! ```
+//! use sized_number::{Context, ContextMut, Endian};
+//!
+//! let mut buffer = b"\x00\x00\x00\x00".to_vec();
+//! ContextMut::new_at(&mut buffer, 0).write_u16(0x4142, Endian::Little).unwrap();
+//!
+//! assert_eq!(b"\x42\x41\x00\x00".to_vec(), buffer);
+//! ```
 
-// Enable char::from_u32()
-#![feature(assoc_char_funcs)]
-
-use simple_error::{SimpleResult, bail};
-use std::fmt::{LowerHex, LowerExp, Octal, Binary, Display};
-use std::mem;
+use half::{bf16, f16};
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::{LowerExp, Display, Write};
+use std::str::FromStr;
 
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Deserialize};
 
 pub mod context;
-pub use context::{Context, Endian};
+pub use context::{Context, Endian, Segment};
+
+pub mod error;
+pub use error::{SizedError, SizedResult};
+
+pub mod context_mut;
+pub use context_mut::ContextMut;
+
+pub mod sized_value;
+pub use sized_value::SizedValue;
+
+pub mod sized_array;
+pub use sized_array::SizedArray;
+
+pub mod hexdump;
+pub use hexdump::{hexdump, write_hexdump, HexdumpOptions, HexdumpText};
+
+pub mod stream_context;
+pub use stream_context::{Stream, StreamContext};
+
+pub mod float;
+pub use float::{Float80, Float128};
+use float::FloatFormat;
+
+#[cfg(feature = "mmap")]
+pub mod mapped_file;
+#[cfg(feature = "mmap")]
+pub use mapped_file::MappedFile;
 
 /// Configure display options for [`SizedDisplay::Scientific`]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct ScientificOptions {
     /// Print the `e` in the scientific notation will be uppercase (`1E0`
@@ -82,16 +136,8 @@ pub struct ScientificOptions {
     pub uppercase: bool,
 }
 
-impl Default for ScientificOptions {
-    fn default() -> Self {
-        Self {
-            uppercase: false,
-        }
-    }
-}
-
 /// Configure display options for [`SizedDisplay::Hex`]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct HexOptions {
     /// Print hex characters uppercase - `1A2B` vs `1a2b`.
@@ -102,6 +148,14 @@ pub struct HexOptions {
 
     /// Zero-pad hex strings to the full width - `0001` vs `1`)
     pub padded: bool,
+
+    /// Display floats in C99 hex-float notation (`0x1.8p+1`) instead of as
+    /// their raw bits (`0x40400000`). When padded, every mantissa digit is
+    /// shown (`0x1.800000p+1`). Has no effect on integers.
+    ///
+    /// NaNs are shown as `nan`, without their payload, so parsing one back
+    /// gives the default quiet NaN.
+    pub hex_float: bool,
 }
 
 impl Default for HexOptions {
@@ -110,12 +164,13 @@ impl Default for HexOptions {
             uppercase: false,
             prefix: true,
             padded: true,
+            hex_float: false,
         }
     }
 }
 
 /// Configure display options for [`SizedDisplay::Octal`]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct OctalOptions {
     /// Prefix octal strings with `0o`
@@ -135,7 +190,7 @@ impl Default for OctalOptions {
 }
 
 /// Configure display options for [`SizedDisplay::Binary`]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct BinaryOptions {
     /// Prefix binary strings with `0b`
@@ -143,6 +198,11 @@ pub struct BinaryOptions {
 
     /// Zero-pad binary strings to the full width - `00000001` vs `1`
     pub padded: bool,
+
+    /// Display bitfields in place within their container, with `.` for the
+    /// bits that aren't part of the field - `0b.....101` vs `0b101`. Has no
+    /// effect on anything that isn't a [`SizedDefinition::Bits`].
+    pub in_place: bool,
 }
 
 impl Default for BinaryOptions {
@@ -150,6 +210,72 @@ impl Default for BinaryOptions {
         Self {
             padded: true,
             prefix: true,
+            in_place: false,
+        }
+    }
+}
+
+/// How [`SizedDisplay::Character`] turns a value into a character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum CharEncoding {
+    /// Values up to `0x7f` are characters
+    Ascii,
+
+    /// Values up to `0xff` are characters, from ISO-8859-1
+    Latin1,
+
+    /// Values are Unicode code points
+    Unicode,
+}
+
+/// Configure display options for [`SizedDisplay::Character`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CharOptions {
+    /// Which values are characters - anything else is escaped
+    pub encoding: CharEncoding,
+}
+
+impl Default for CharOptions {
+    fn default() -> Self {
+        Self {
+            encoding: CharEncoding::Ascii,
+        }
+    }
+}
+
+/// Configure how [`SizedDefinition::to_string_array`] lays out its values
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ArrayOptions {
+    /// Put between values - `", "` by default. Trailing whitespace is
+    /// dropped at the end of a line.
+    pub separator: String,
+
+    /// Surround the values with `[` and `]`
+    pub brackets: bool,
+
+    /// Start a new line after this many values (`None` or `Some(0)` keeps
+    /// everything on one line)
+    pub per_line: Option<usize>,
+
+    /// Only display this many values, followed by `… N more`
+    pub max_elements: Option<usize>,
+
+    /// Start each line with the position of its first value, in hex
+    /// (`00000010: `)
+    pub offsets: bool,
+}
+
+impl Default for ArrayOptions {
+    fn default() -> Self {
+        Self {
+            separator: ", ".to_string(),
+            brackets: true,
+            per_line: None,
+            max_elements: None,
+            offsets: false,
         }
     }
 }
@@ -160,7 +286,7 @@ impl Default for BinaryOptions {
 /// decisions based on the datatype. When displaying a padded hex value, for
 /// example, it's padded to the exact width of the field, no matter what that
 /// is.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum SizedDisplay {
     /// Display in hexadecimal.
@@ -179,14 +305,28 @@ pub enum SizedDisplay {
     ///     uppercase: true,
     ///     prefix: false,
     ///     padded: true,
+    ///     hex_float: false,
     /// })).unwrap());
     ///
     /// assert_eq!("0xab", d.to_string(context, SizedDisplay::Hex(HexOptions {
     ///     uppercase: false,
     ///     prefix: true,
     ///     padded: false,
+    ///     hex_float: false,
     /// })).unwrap());
     ///
+    /// // Floats display their raw bits, or as a C99 hex float
+    /// let buffer = b"\x40\x40\x00\x00".to_vec();
+    /// let context = Context::new_at(&buffer, 0);
+    /// let d = SizedDefinition::F32(Endian::Big);
+    ///
+    /// assert_eq!("0x40400000", d.to_string(context, SizedDisplay::Hex(HexOptions::default())).unwrap());
+    /// assert_eq!("0x1.8p+1", d.to_string(context, SizedDisplay::Hex(HexOptions {
+    ///     uppercase: false,
+    ///     prefix: true,
+    ///     padded: false,
+    ///     hex_float: true,
+    /// })).unwrap());
     /// ```
     Hex(HexOptions),
 
@@ -246,6 +386,54 @@ pub enum SizedDisplay {
     /// assert_eq!("1e2", SizedDefinition::U8.to_string(context, SizedDisplay::Scientific(Default::default())).unwrap());
     /// ```
     Scientific(ScientificOptions),
+
+    /// Display the IEEE-754 fields of a float: the sign, the exponent (biased
+    /// and unbiased), the mantissa, and what kind of value it is (normal,
+    /// subnormal, zero, infinity, quiet NaN, or signalling NaN). NaNs include
+    /// their payload.
+    ///
+    /// This is for display only - it can't be used with integers, or parsed
+    /// back with [`SizedDefinition::from_string`].
+    ///
+    /// Example:
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x40\x40\x00\x00\x7f\x80\x00\x01".to_vec();
+    /// let context = Context::new_at(&buffer, 0);
+    /// let d = SizedDefinition::F32(Endian::Big);
+    ///
+    /// assert_eq!("sign=+ exponent=128 (unbiased +1) mantissa=0x400000 normal", d.to_string(context, SizedDisplay::FloatParts).unwrap());
+    /// assert_eq!("sign=+ exponent=255 (unbiased +128) mantissa=0x000001 signalling NaN (payload 0x1)", d.to_string(context.at(4), SizedDisplay::FloatParts).unwrap());
+    /// ```
+    FloatParts,
+
+    /// Display an integer as a single character, with C-style escapes for
+    /// anything that isn't printable: `\0`, `\t`, `\n`, `\r`, and `\\`,
+    /// then `\x1b` for other values up to `0xff`, and `\u{…}` for bigger
+    /// ones. Values that aren't a character in the chosen
+    /// [`CharEncoding`] are escaped too.
+    ///
+    /// Signed values use their two's complement bits, and floats can't be
+    /// displayed as characters.
+    ///
+    /// Example:
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"A\n\xe9\x00\x01\xf6\x00".to_vec();
+    /// let context = Context::new_at(&buffer, 0);
+    /// let latin1 = SizedDisplay::Character(CharOptions { encoding: CharEncoding::Latin1 });
+    /// let unicode = SizedDisplay::Character(CharOptions { encoding: CharEncoding::Unicode });
+    ///
+    /// assert_eq!("A", SizedDefinition::U8.to_string(context, SizedDisplay::Character(Default::default())).unwrap());
+    /// assert_eq!("\\n", SizedDefinition::U8.to_string(context.at(1), SizedDisplay::Character(Default::default())).unwrap());
+    /// assert_eq!("\\xe9", SizedDefinition::U8.to_string(context.at(2), SizedDisplay::Character(Default::default())).unwrap());
+    /// assert_eq!("é", SizedDefinition::U8.to_string(context.at(2), latin1).unwrap());
+    /// assert_eq!("\\u{1f600}", SizedDefinition::U32(Endian::Big).to_string(context.at(3), latin1).unwrap());
+    /// assert_eq!("😀", SizedDefinition::U32(Endian::Big).to_string(context.at(3), unicode).unwrap());
+    /// ```
+    Character(CharOptions),
 }
 
 /// Define how data is read from a Context.
@@ -254,7 +442,20 @@ pub enum SizedDisplay {
 /// memory.
 ///
 /// The options all pretty cleanly map to the equivalent datatypes.
-#[derive(Debug, Clone, Copy)]
+///
+/// A definition displays as (and parses from) a compact type name - use
+/// `format!("{}", d)` rather than `d.to_string()`, which reads a value (see
+/// [`SizedDefinition::to_string`]):
+///
+/// ```
+/// use sized_number::{SizedDefinition, Endian};
+///
+/// let d = SizedDefinition::U32(Endian::Big);
+///
+/// assert_eq!("u32be", format!("{}", d));
+/// assert_eq!(d, "u32be".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum SizedDefinition {
     /// Unsigned 8-bit integer
@@ -263,6 +464,9 @@ pub enum SizedDefinition {
     /// Unsigned 16-bit integer
     U16(Endian),
 
+    /// Unsigned 24-bit integer (3 bytes), common for audio samples
+    U24(Endian),
+
     /// Unsigned 32-bit integer
     U32(Endian),
 
@@ -272,12 +476,23 @@ pub enum SizedDefinition {
     /// Unsigned 128-bit integer
     U128(Endian),
 
+    /// Unsigned integer of any width from 1 to 16 bytes - for example,
+    /// `UN(6, Endian::Big)` is a 48-bit integer. Widths outside that range
+    /// fail to read.
+    ///
+    /// Note: when the width matches one of the other types, this displays as
+    /// that type's name (so `UN(2, ...)` is `u16le` or `u16be`).
+    UN(u8, Endian),
+
     /// Signed 8-bit integer
     I8,
 
     /// Signed 16-bit integer
     I16(Endian),
 
+    /// Signed 24-bit integer (3 bytes), common for audio samples
+    I24(Endian),
+
     /// Signed 32-bit integer
     I32(Endian),
 
@@ -287,370 +502,1186 @@ pub enum SizedDefinition {
     /// Signed 128-bit integer
     I128(Endian),
 
+    /// Signed integer of any width from 1 to 16 bytes, with the sign
+    /// extended - see [`SizedDefinition::UN`].
+    IN(u8, Endian),
+
+    /// A bitfield - `width` bits, starting `offset` bits up from the least
+    /// significant bit, of an unsigned integer that's `container` bytes
+    /// long. When `signed` is set, the field's top bit is its sign.
+    ///
+    /// For example, "bits 3 through 7 of a big-endian u16" is:
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let d = SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 3, width: 5, signed: false };
+    ///
+    /// let buffer = b"\x00\xa8".to_vec();
+    /// let context = Context::new(&buffer);
+    ///
+    /// assert_eq!("21", d.to_string(context, SizedDisplay::Decimal).unwrap());
+    /// assert_eq!("0b........10101...", d.to_string(context, SizedDisplay::Binary(BinaryOptions {
+    ///     prefix: true,
+    ///     padded: true,
+    ///     in_place: true,
+    /// })).unwrap());
+    /// ```
+    ///
+    /// Hex, octal, and binary display (and parse) the field by itself, padded
+    /// to its width, unless it's displayed in place. Parsing a bitfield sets
+    /// the other bits of the container to zero.
+    Bits {
+        /// The size of the containing integer, in bytes (1 to 16)
+        container: u8,
+
+        /// The endianness of the containing integer
+        endian: Endian,
+
+        /// The first bit of the field, where 0 is the least significant bit
+        offset: u8,
+
+        /// The number of bits in the field
+        width: u8,
+
+        /// Whether to extend the field's sign
+        signed: bool,
+    },
+
+    /// Signed 16-bit (aka, half precision or IEEE-754 binary16) floating point.
+    ///
+    /// Note: when displayed as hex, octal, or binary, floats show their raw
+    /// IEEE-754 bits (or, optionally, C99 hex-float notation).
+    F16(Endian),
+
+    /// Signed 16-bit "brain" floating point (bfloat16) - the top half of a
+    /// single precision float, with 8 bits of exponent and 7 of mantissa.
+    ///
+    /// Note: when displayed as hex, octal, or binary, floats show their raw
+    /// bits (or, optionally, C99 hex-float notation).
+    BF16(Endian),
+
     /// Signed 32-bit (aka, single precision) floating point.
     ///
-    /// Note: floats can only be displayed as `SizedDisplay::Decimal` or
-    /// `SizedDisplay::Scientific`.
+    /// Note: when displayed as hex, octal, or binary, floats show their raw
+    /// IEEE-754 bits (or, optionally, C99 hex-float notation).
     F32(Endian),
 
     /// Signed 64-bit (aka, double precision) floating point
     ///
-    /// Note: floats can only be displayed as `SizedDisplay::Decimal` or
-    /// `SizedDisplay::Scientific`.
+    /// Note: when displayed as hex, octal, or binary, floats show their raw
+    /// IEEE-754 bits (or, optionally, C99 hex-float notation).
     F64(Endian),
+
+    /// Signed 80-bit x87 extended precision floating point (a `long double`
+    /// on x86), stored in 10 bytes. See [`Float80`].
+    ///
+    /// Note: when displayed as hex, octal, or binary, floats show their raw
+    /// bits (or, optionally, C99 hex-float notation).
+    F80(Endian),
+
+    /// Signed 128-bit (aka, quad precision or IEEE-754 binary128) floating
+    /// point. See [`Float128`].
+    ///
+    /// Note: when displayed as hex, octal, or binary, floats show their raw
+    /// IEEE-754 bits (or, optionally, C99 hex-float notation).
+    F128(Endian),
+
+    /// Unsigned LEB128 (aka, a protobuf varint) - 7 bits per byte, least
+    /// significant first, up to 64 bits. Used by DWARF, WebAssembly, and
+    /// Android DEX.
+    ///
+    /// Variable-length integers read as a [`SizedValue::U64`] or
+    /// [`SizedValue::I64`], and their size depends on the data - see
+    /// [`SizedDefinition::size`].
+    ULEB128,
+
+    /// Signed LEB128 - see [`SizedDefinition::ULEB128`].
+    SLEB128,
+
+    /// Protobuf zigzag-encoded signed varint (`sint32` / `sint64`) - see
+    /// [`Context::read_zigzag`].
+    ZigZag,
+
+    /// Git-style varint, most significant 7 bits first - see
+    /// [`Context::read_git_varint`].
+    GitVarint,
 }
 
-/// An internal function to help with displaying hex.
-///
-/// Unfortunately, I don't know of a way to require both [`UpperHex`] and
-/// [`LowerHex`] traits, so I do some manual formatting :-/
-fn display_hex(v: Box<dyn LowerHex>, options: HexOptions) -> String {
-    let v = v.as_ref();
-
-    let mut h = match options.padded {
-        // No padding is easy
-        false => format!("{:x}",   v),
-
-        // Padding requires a bit more tinkering to do dynamically
-        true => {
-            match (options.padded, mem::size_of_val(v) * 2) {
-                (true, 2)   => format!(  "{:02x}",  v),
-                (true, 4)   => format!(  "{:04x}",  v),
-                (true, 8)   => format!(  "{:08x}",  v),
-                (true, 16)  => format!(  "{:016x}", v),
-                (true, 32)  => format!(  "{:032x}", v),
-
-                // When not padded, or in doubt about length, just print normally
-                (_, _)      => format!(  "{:x}",     v),
-            }
+/// An internal [`fmt::Write`] that uppercases (ASCII) everything written
+/// through it, so uppercase output doesn't need a second [`String`].
+struct Uppercase<'a>(&'a mut dyn fmt::Write);
+
+impl fmt::Write for Uppercase<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.0.write_char(c.to_ascii_uppercase())?;
         }
-    };
 
-    // There's no way to make the parameter both LowerHex and UpperHex
-    if options.uppercase {
-        h = h.to_uppercase();
+        Ok(())
     }
+}
 
+/// An internal function to help with displaying hex.
+///
+/// `v` is the raw bits, and `bits` is the width of the field (for padding).
+/// Signed values should already be converted to their two's complement bit
+/// pattern.
+fn write_hex(f: &mut dyn fmt::Write, v: u128, bits: u32, options: HexOptions) -> fmt::Result {
     if options.prefix {
-        h = format!("0x{}", h);
+        f.write_str("0x")?;
     }
 
-    h
+    let width = bits.div_ceil(4) as usize;
+    match (options.padded, options.uppercase) {
+        (false, false) => write!(f, "{:x}", v),
+        (false, true)  => write!(f, "{:X}", v),
+        (true,  false) => write!(f, "{:0width$x}", v, width = width),
+        (true,  true)  => write!(f, "{:0width$X}", v, width = width),
+    }
 }
 
 /// An internal function to help with displaying decimal
-fn display_decimal(v: Box<dyn Display>) -> String {
-    format!("{}", v.as_ref())
+fn write_decimal(f: &mut dyn fmt::Write, v: impl Display) -> fmt::Result {
+    write!(f, "{}", v)
 }
 
-/// An internal function to help with displaying octal
-fn display_octal(v: Box<dyn Octal>, options: OctalOptions) -> String {
-    let v = v.as_ref();
-
-    if options.padded {
-        match (options.prefix, mem::size_of_val(v)) {
-            (false, 1)  => format!("{:03o}", v),
-            (false, 2)  => format!("{:06o}", v),
-            (false, 4)  => format!("{:011o}", v),
-            (false, 8)  => format!("{:022o}", v),
-            (false, 16) => format!("{:043o}", v),
-            (false, _) => format!("{:o}", v),
-
-            (true,  1)  => format!("0o{:03o}", v),
-            (true,  2)  => format!("0o{:06o}", v),
-            (true,  4)  => format!("0o{:011o}", v),
-            (true,  8)  => format!("0o{:022o}", v),
-            (true,  16) => format!("0o{:043o}", v),
+/// An internal function to help with displaying octal. Like
+/// [`write_hex`], `v` is the raw bits and `bits` is the width.
+fn write_octal(f: &mut dyn fmt::Write, v: u128, bits: u32, options: OctalOptions) -> fmt::Result {
+    match (options.padded, options.prefix) {
+        (false, false) => write!(f, "{:o}", v),
+        (false, true)  => write!(f, "0o{:o}", v),
+        (true,  false) => write!(f, "{:0width$o}", v, width = bits.div_ceil(3) as usize),
+        (true,  true)  => write!(f, "0o{:0width$o}", v, width = bits.div_ceil(3) as usize),
+    }
+}
 
-            (true,   _) => format!("0o{:o}", v),
-        }
-    } else {
-        match options.prefix {
-            false => format!("{:o}", v),
-            true  => format!("0o{:o}", v),
-        }
+/// An internal function to help with displaying binary. Like
+/// [`write_hex`], `v` is the raw bits and `bits` is the width.
+fn write_binary(f: &mut dyn fmt::Write, v: u128, bits: u32, options: BinaryOptions) -> fmt::Result {
+    match (options.padded, options.prefix) {
+        (false, false) => write!(f, "{:b}", v),
+        (false, true)  => write!(f, "0b{:b}", v),
+        (true,  false) => write!(f, "{:0width$b}", v, width = bits as usize),
+        (true,  true)  => write!(f, "0b{:0width$b}", v, width = bits as usize),
     }
 }
 
-/// An internal function to help with displaying binary
-fn display_binary(v: Box<dyn Binary>, options: BinaryOptions) -> String {
-    let v = v.as_ref();
+/// An internal function to display a bitfield in place within its
+/// container, with a `.` for each bit that isn't part of the field.
+fn write_binary_in_place(f: &mut dyn fmt::Write, v: u128, container_bits: u32, offset: u32, width: u32, options: BinaryOptions) -> fmt::Result {
+    if options.prefix {
+        f.write_str("0b")?;
+    }
 
-    match (options.padded, options.prefix) {
-        (false, false) => format!("{:b}", v),
-        (false, true ) => format!("0b{:b}", v),
-        (true, false) => {
-            match mem::size_of_val(v) * 8 {
-                8   => format!("{:08b}",   v),
-                16  => format!("{:016b}",  v),
-                32  => format!("{:032b}",  v),
-                64  => format!("{:064b}",  v),
-                128 => format!("{:0128b}", v),
-                _   => format!("{:b}",     v),
-            }
-        },
-        (true, true) => {
-            match mem::size_of_val(v) * 8 {
-                8   => format!("0b{:08b}",   v),
-                16  => format!("0b{:016b}",  v),
-                32  => format!("0b{:032b}",  v),
-                64  => format!("0b{:064b}",  v),
-                128 => format!("0b{:0128b}", v),
-                _   => format!("0b{:b}",     v),
-            }
-        }
+    for bit in (0..container_bits).rev() {
+        f.write_char(match bit.checked_sub(offset) {
+            Some(bit) if bit < width => if (v >> bit) & 1 == 1 { '1' } else { '0' },
+            _ => '.',
+        })?;
     }
+
+    Ok(())
 }
 
 /// An internal function to help with displaying scientific / exponential
 /// notation.
-fn display_scientific(v: Box<dyn LowerExp>, options: ScientificOptions) -> String {
-    let mut v = format!("{:e}", v.as_ref());
-
-    if options.uppercase {
-        v = v.to_uppercase();
+fn write_scientific(f: &mut dyn fmt::Write, v: impl LowerExp, options: ScientificOptions) -> fmt::Result {
+    match options.uppercase {
+        false => write!(f, "{:e}", v),
+        true  => write!(Uppercase(f), "{:e}", v),
     }
-
-    v
 }
 
-impl SizedDefinition {
-    /// Returns the size, in bytes, of the current type.
-    pub fn size(self) -> u64 {
-        match self {
-            Self::U8      => 1,
-            Self::U16(_)  => 2,
-            Self::U32(_)  => 4,
-            Self::U64(_)  => 8,
-            Self::U128(_) => 16,
+/// An internal function to display the raw bits of a float in C99 hex-float
+/// notation (like `printf("%a")`).
+///
+/// Since this is about looking at the bits, subnormal values are shown the
+/// way they're stored (`0x0.8p-126`) rather than normalized. The same goes
+/// for x87 unnormals, which have a leading `0` but a normal exponent.
+///
+/// NaNs are displayed as just `nan`, without their payload, so they parse
+/// back as the default quiet NaN (use [`SizedDisplay::FloatParts`] to see the
+/// payload).
+fn write_hex_float(f: &mut dyn fmt::Write, bits: u128, format: FloatFormat, options: HexOptions) -> fmt::Result {
+    let (negative, exponent, integer, mantissa) = format.fields(bits);
+    let bias = format.bias();
+
+    if negative {
+        f.write_char('-')?;
+    }
 
-            Self::I8      => 1,
-            Self::I16(_)  => 2,
-            Self::I32(_)  => 4,
-            Self::I64(_)  => 8,
-            Self::I128(_) => 16,
+    // Infinity and NaN don't have a hex representation
+    if exponent == format.max_exponent() {
+        let special = if integer && mantissa == 0 { "inf" } else { "nan" };
 
-            Self::F32(_)  => 4,
-            Self::F64(_)  => 8,
-        }
+        return match options.uppercase {
+            false => f.write_str(special),
+            true  => Uppercase(f).write_str(special),
+        };
     }
 
-    /// Read data from the context, based on the [`SizedDefinition`], and
-    /// display it based on the `SizedDisplay`
-    pub fn to_string(self, context: Context, display: SizedDisplay) -> SimpleResult<String> {
-        match self {
-            Self::U8 => {
-                let v = Box::new(context.read_u8()?);
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    let (leading, exponent) = match (integer, mantissa) {
+        (false, 0) => (0, 0),
+        _          => (integer as u8, exponent.max(1) as i64 - bias),
+    };
 
-            Self::U16(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_u16(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_u16(Endian::Little)?),
-                };
+    // Line the mantissa up on a hex digit, then drop the trailing zero
+    // digits unless we're padding
+    let mantissa_bits = format.mantissa_bits;
+    let digits = mantissa_bits.div_ceil(4);
+    let mantissa = mantissa << (digits * 4 - mantissa_bits);
+    let trimmed = match (options.padded, mantissa) {
+        (true,  _) => 0,
+        (false, 0) => digits,
+        (false, m) => m.trailing_zeros() / 4,
+    };
+    let mantissa = mantissa.checked_shr(trimmed * 4).unwrap_or(0);
+    let digits = (digits - trimmed) as usize;
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    if options.prefix {
+        f.write_str("0x")?;
+    }
+    write!(f, "{}", leading)?;
 
-            Self::U32(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_u32(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_u32(Endian::Little)?),
-                };
+    match (digits, options.uppercase) {
+        (0, _)     => (),
+        (_, false) => write!(f, ".{:0width$x}", mantissa, width = digits)?,
+        (_, true)  => write!(f, ".{:0width$X}", mantissa, width = digits)?,
+    };
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    write!(f, "p{:+}", exponent)
+}
 
-            Self::U64(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_u64(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_u64(Endian::Little)?),
-                };
+/// An internal function to display the fields of a float - the sign, exponent
+/// (biased and unbiased), and mantissa - along with what kind of value it is.
+///
+/// x87 extended floats also show their integer bit, and can be one of the
+/// encodings that IEEE-754 doesn't have: pseudo-denormal, unnormal,
+/// pseudo-infinity, or pseudo-NaN.
+fn write_float_parts(f: &mut dyn fmt::Write, bits: u128, format: FloatFormat) -> fmt::Result {
+    let (negative, exponent, integer, mantissa) = format.fields(bits);
+    let bias = format.bias();
+    let quiet_bit = 1 << (format.mantissa_bits - 1);
+    let max_exponent = format.max_exponent();
+
+    // Zero and subnormals use the minimum exponent, not 0 - bias
+    let unbiased = exponent.max(1) as i64 - bias;
+
+    write!(f, "sign={} exponent={} (unbiased {:+}) ", if negative { "-" } else { "+" }, exponent, unbiased)?;
+    if format.explicit_integer {
+        write!(f, "integer={} ", integer as u8)?;
+    }
+    write!(f, "mantissa=0x{:0width$x} ", mantissa, width = format.mantissa_bits.div_ceil(4) as usize)?;
+
+    match (exponent, integer, mantissa) {
+        (0, false, 0) => f.write_str("zero"),
+        (0, false, _) => f.write_str("subnormal"),
+        (0, true,  _) => f.write_str("pseudo-denormal"),
+        (e, false, 0) if e == max_exponent => f.write_str("pseudo-infinity"),
+        (e, false, _) if e == max_exponent => f.write_str("pseudo-NaN"),
+        (e, true,  0) if e == max_exponent => f.write_str("infinity"),
+        (e, true,  m) if e == max_exponent => {
+            match m & quiet_bit {
+                0 => write!(f, "signalling NaN (payload 0x{:x})", m & (quiet_bit - 1)),
+                _ => write!(f, "quiet NaN (payload 0x{:x})", m & (quiet_bit - 1)),
+            }
+        },
+        (_, false, _) => f.write_str("unnormal"),
+        (_, true,  _) => f.write_str("normal"),
+    }
+}
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+/// An internal function to display a value as a character, escaping it if
+/// it's not printable (or isn't a character in the encoding).
+///
+/// Like [`write_hex`], `v` is the raw bits.
+fn write_character(f: &mut dyn fmt::Write, v: u128, options: CharOptions) -> fmt::Result {
+    let c = match options.encoding {
+        CharEncoding::Ascii if v < 0x80   => char::from_u32(v as u32),
+        CharEncoding::Latin1 if v < 0x100 => char::from_u32(v as u32),
+        CharEncoding::Unicode             => u32::try_from(v).ok().and_then(char::from_u32),
+        _                                 => None,
+    };
 
-            Self::U128(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_u128(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_u128(Endian::Little)?),
-                };
+    match c {
+        Some('\0') => f.write_str("\\0"),
+        Some('\t') => f.write_str("\\t"),
+        Some('\n') => f.write_str("\\n"),
+        Some('\r') => f.write_str("\\r"),
+        Some('\\') => f.write_str("\\\\"),
+        Some(c) if !c.is_control() => f.write_char(c),
+        _ if v < 0x100 => write!(f, "\\x{:02x}", v),
+        _              => write!(f, "\\u{{{:x}}}", v),
+    }
+}
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+/// An internal function to parse a character, as displayed by
+/// [`write_character`], back into its value.
+fn parse_character(s: &str, options: CharOptions) -> SizedResult<u128> {
+    let v = match s.strip_prefix('\\') {
+        Some("0")  => 0,
+        Some("t")  => '\t' as u128,
+        Some("n")  => '\n' as u128,
+        Some("r")  => '\r' as u128,
+        Some("\\") => '\\' as u128,
+        Some(escape) => {
+            if let Some(digits) = escape.strip_prefix('x').filter(|digits| digits.len() == 2) {
+                return parse_radix(digits, 16);
+            }
 
-            Self::I8 => {
-                let v = Box::new(context.read_i8()?);
+            match escape.strip_prefix("u{").and_then(|e| e.strip_suffix('}')) {
+                Some(digits) => return parse_radix(digits, 16),
+                None         => return Err(SizedError::parse(s, "Unknown escape")),
+            }
+        },
+        None => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c as u128,
+                _ => return Err(SizedError::parse(s, "Not a single character")),
+            }
+        },
+    };
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    match options.encoding {
+        CharEncoding::Ascii if v >= 0x80   => Err(SizedError::parse(s, "Not an ASCII character")),
+        CharEncoding::Latin1 if v >= 0x100 => Err(SizedError::parse(s, "Not a Latin-1 character")),
+        _ => Ok(v),
+    }
+}
 
-            Self::I16(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_i16(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_i16(Endian::Little)?),
-                };
+/// An internal function to strip an (optional) prefix from a string.
+///
+/// When `required` is set, the prefix must be present; otherwise, it must not
+/// be, since that's how it would have been displayed.
+fn strip_prefix<'a>(s: &'a str, prefix: &str, required: bool) -> SizedResult<&'a str> {
+    match (required, s.strip_prefix(prefix)) {
+        (true,  Some(s)) => Ok(s),
+        (true,  None)    => Err(SizedError::parse(s, format!("Missing prefix \"{}\"", prefix))),
+        (false, Some(_)) => Err(SizedError::parse(s, format!("Unexpected prefix \"{}\"", prefix))),
+        (false, None)    => Ok(s),
+    }
+}
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+/// An internal function to parse a bitfield displayed by
+/// [`write_binary_in_place`] back into the field's value.
+fn parse_binary_in_place(s: &str, container_bits: u32, offset: u32, width: u32, options: BinaryOptions) -> SizedResult<u128> {
+    let bits = strip_prefix(s, "0b", options.prefix)?;
+    if bits.len() != container_bits as usize {
+        return Err(SizedError::parse(s, format!("Bitfield should be {} characters", container_bits)));
+    }
 
-            Self::I32(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_i32(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_i32(Endian::Little)?),
-                };
+    let mut v = 0;
+    for (c, bit) in bits.chars().zip((0..container_bits).rev()) {
+        match (c, bit >= offset && bit < offset + width) {
+            ('.', false) => (),
+            ('0', true)  => v <<= 1,
+            ('1', true)  => v = (v << 1) | 1,
+            _ => return Err(SizedError::parse(s, "Not a valid bitfield")),
+        }
+    }
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    Ok(v)
+}
 
-            Self::I64(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_i64(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_i64(Endian::Little)?),
-                };
+/// An internal function to parse unsigned digits in the given radix.
+///
+/// Unlike [`u128::from_str_radix`], this doesn't permit a leading `+` or
+/// `-`, since we never display one.
+fn parse_radix(digits: &str, radix: u32) -> SizedResult<u128> {
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(SizedError::parse(digits, format!("Not a valid base-{} number", radix)));
+    }
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    match u128::from_str_radix(digits, radix) {
+        Ok(v)  => Ok(v),
+        Err(_) => Err(SizedError::Overflow { value: digits.to_string(), bits: 128 }),
+    }
+}
 
-            Self::I128(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_i128(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_i128(Endian::Little)?),
-                };
+/// An internal function to parse scientific / exponential notation into an
+/// integer, without losing any precision.
+///
+/// Returns whether or not the value is negative, and its magnitude.
+fn parse_scientific(s: &str) -> SizedResult<(bool, u128)> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None    => (false, s),
+    };
 
-                match display {
-                    SizedDisplay::Hex(options)        => Ok(display_hex(v, options)),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(options)      => Ok(display_octal(v, options)),
-                    SizedDisplay::Binary(options)     => Ok(display_binary(v, options)),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], &s[(i + 1)..]),
+        None    => return Err(SizedError::parse(s, "Missing exponent")),
+    };
 
-            Self::F32(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_f32(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_f32(Endian::Little)?),
-                };
+    let (whole, fraction) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], &mantissa[(i + 1)..]),
+        None    => (mantissa, ""),
+    };
 
-                match display {
-                    SizedDisplay::Hex(_)              => bail!("Floats can't be displayed as hex"),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(_)            => bail!("Floats can't be displayed as octal"),
-                    SizedDisplay::Binary(_)           => bail!("Floats can't be displayed as binary"),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    if whole.is_empty() || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(SizedError::parse(mantissa, "Not a valid number"));
+    }
 
-            Self::F64(endian) => {
-                let v = match endian {
-                    Endian::Big => Box::new(context.read_f64(Endian::Big)?),
-                    Endian::Little => Box::new(context.read_f64(Endian::Little)?),
-                };
+    let exponent: i64 = match exponent.parse() {
+        Ok(e) if !exponent.starts_with('+') => e,
+        _ => return Err(SizedError::parse(exponent, "Not a valid exponent")),
+    };
 
-                match display {
-                    SizedDisplay::Hex(_)              => bail!("Floats can't be displayed as hex"),
-                    SizedDisplay::Decimal             => Ok(display_decimal(v)),
-                    SizedDisplay::Octal(_)            => bail!("Floats can't be displayed as octal"),
-                    SizedDisplay::Binary(_)           => bail!("Floats can't be displayed as binary"),
-                    SizedDisplay::Scientific(options) => Ok(display_scientific(v, options)),
-                }
-            },
+    // Shift the decimal point to the end of the mantissa, then apply what's
+    // left of the exponent
+    let mut digits = format!("{}{}", whole, fraction);
+    let exponent = match exponent.checked_sub(fraction.len() as i64) {
+        Some(e) => e,
+        None    => return Err(SizedError::parse(s, "Not a valid exponent")),
+    };
+
+    if exponent < 0 {
+        let drop = usize::try_from(exponent.unsigned_abs()).unwrap_or(usize::MAX);
+        let keep = digits.len().saturating_sub(drop);
+        if digits[keep..].chars().any(|c| c != '0') {
+            return Err(SizedError::parse(s, "Not an integer"));
         }
+        digits.truncate(keep);
+    } else if parse_radix(&digits, 10)? != 0 {
+        // Don't bother padding zero, since 0e1000 is still zero
+        if exponent > 39 {
+            return Err(SizedError::Overflow { value: s.to_string(), bits: 128 });
+        }
+        digits.push_str(&"0".repeat(exponent as usize));
     }
 
-    /// Returns `true` for variants that can succeed in [`to_u64`]
-    pub fn can_be_u64(self) -> bool {
-        match self {
-            Self::U8      => true,
-            Self::U16(_)  => true,
+    if digits.is_empty() {
+        return Ok((negative, 0));
+    }
+
+    Ok((negative, parse_radix(&digits, 10)?))
+}
+
+/// An internal function to make sure an integer being parsed is 1 to 128
+/// bits wide.
+fn check_parse_bits(bits: u32) -> SizedResult<()> {
+    if bits == 0 || bits > 128 {
+        return Err(SizedError::InvalidArgument(format!("Integer width must be 1 to 128 bits: {}", bits)));
+    }
+
+    Ok(())
+}
+
+/// An internal function to parse a string, as displayed by `display`, into an
+/// unsigned integer no bigger than `bits` bits.
+fn parse_unsigned(s: &str, display: SizedDisplay, bits: u32) -> SizedResult<u128> {
+    check_parse_bits(bits)?;
+
+    let v = match display {
+        SizedDisplay::Hex(options)    => parse_radix(strip_prefix(s, "0x", options.prefix)?, 16)?,
+        SizedDisplay::Decimal         => parse_radix(s, 10)?,
+        SizedDisplay::Octal(options)  => parse_radix(strip_prefix(s, "0o", options.prefix)?, 8)?,
+        SizedDisplay::Binary(options) => parse_radix(strip_prefix(s, "0b", options.prefix)?, 2)?,
+        SizedDisplay::Scientific(_)   => {
+            match parse_scientific(s)? {
+                (true, 0)  => 0,
+                (true, _)  => return Err(SizedError::parse(s, "Unsigned values can't be negative")),
+                (false, v) => v,
+            }
+        },
+        SizedDisplay::FloatParts      => return Err(SizedError::parse(s, "Float parts can't be parsed")),
+        SizedDisplay::Character(options) => parse_character(s, options)?,
+    };
+
+    if bits < 128 && v >> bits != 0 {
+        return Err(SizedError::Overflow { value: s.to_string(), bits });
+    }
+
+    Ok(v)
+}
+
+/// An internal function to parse a string, as displayed by `display`, into a
+/// signed integer no bigger than `bits` bits.
+///
+/// Hex, octal, and binary are displayed as the two's complement bit pattern
+/// (so `-1i8` is `0xff`), so that's how we read them back.
+fn parse_signed(s: &str, display: SizedDisplay, bits: u32) -> SizedResult<i128> {
+    check_parse_bits(bits)?;
+
+    let (negative, magnitude) = match display {
+        SizedDisplay::Decimal => {
+            match s.strip_prefix('-') {
+                Some(digits) => (true, parse_radix(digits, 10)?),
+                None         => (false, parse_radix(s, 10)?),
+            }
+        },
+        SizedDisplay::Scientific(_) => parse_scientific(s)?,
+
+        // Everything else is a bit pattern, so sign-extend it
+        _ => {
+            let v = parse_unsigned(s, display, bits)?;
+            let shift = 128 - bits;
+
+            return Ok(((v << shift) as i128) >> shift);
+        },
+    };
+
+    // The most negative value has a magnitude one larger than the most
+    // positive one
+    let limit = 1u128 << (bits - 1);
+    match negative {
+        true  if magnitude <= limit => Ok((magnitude as i128).wrapping_neg()),
+        false if magnitude < limit  => Ok(magnitude as i128),
+        _ => Err(SizedError::Overflow { value: s.to_string(), bits }),
+    }
+}
+
+/// An internal function to parse a floating point value, as displayed by
+/// `display`, into its raw bits.
+///
+/// `to_bits` converts the parsed value (for decimal and scientific) into its
+/// bits; everything else is already bits.
+fn parse_float<T, F>(s: &str, display: SizedDisplay, format: FloatFormat, to_bits: F) -> SizedResult<u128>
+where
+    T: FromStr,
+    F: Fn(T) -> u128,
+{
+    match display {
+        SizedDisplay::Hex(options) if options.hex_float => parse_hex_float(s, format, options),
+        SizedDisplay::Hex(_) | SizedDisplay::Octal(_) | SizedDisplay::Binary(_) => {
+            parse_unsigned(s, display, format.width())
+        },
+        SizedDisplay::FloatParts => Err(SizedError::parse(s, "Float parts can't be parsed")),
+        SizedDisplay::Character(_) => Err(SizedError::parse(s, "Floats can't be parsed from characters")),
+        SizedDisplay::Decimal | SizedDisplay::Scientific(_) => {
+            match s.parse() {
+                Ok(v) => Ok(to_bits(v)),
+                Err(_) => Err(SizedError::parse(s, "Not a valid floating point value")),
+            }
+        },
+    }
+}
+
+/// An internal function to parse C99 hex-float notation, as displayed by
+/// [`write_hex_float`], back into the raw bits of a float.
+fn parse_hex_float(s: &str, format: FloatFormat, options: HexOptions) -> SizedResult<u128> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None    => (false, s),
+    };
+    let mantissa_bits = format.mantissa_bits;
+    let max_exponent = format.max_exponent();
+
+    match unsigned.to_lowercase().as_str() {
+        "inf" => return Ok(format.assemble(negative, max_exponent, true, 0)),
+        "nan" => return Ok(format.assemble(negative, max_exponent, true, 1 << (mantissa_bits - 1))),
+        _     => (),
+    };
+
+    let unsigned = strip_prefix(unsigned, "0x", options.prefix)?;
+    let (mantissa, exponent) = match unsigned.find('p') {
+        Some(i) => (&unsigned[..i], &unsigned[(i + 1)..]),
+        None    => return Err(SizedError::parse(s, "Missing exponent")),
+    };
+
+    if !exponent.starts_with(['+', '-']) || !exponent[1..].chars().all(|c| c.is_ascii_digit()) {
+        return Err(SizedError::parse(exponent, "Not a valid exponent"));
+    }
+    let exponent: i64 = match exponent.parse() {
+        Ok(e) => e,
+        Err(_) => return Err(SizedError::parse(exponent, "Not a valid exponent")),
+    };
+
+    let (leading, fraction) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], &mantissa[(i + 1)..]),
+        None    => (mantissa, ""),
+    };
+
+    // Pad the fraction out to the full width, then make sure it fits
+    let digits = mantissa_bits.div_ceil(4) as usize;
+    let extra_bits = digits as u32 * 4 - mantissa_bits;
+    if fraction.len() > digits || (mantissa.contains('.') && fraction.is_empty()) {
+        return Err(SizedError::parse(mantissa, "Not a valid mantissa"));
+    }
+    let fraction = parse_radix(&format!("{:0<1$}", fraction, digits), 16)?;
+    if fraction & ((1 << extra_bits) - 1) != 0 {
+        return Err(SizedError::parse(mantissa, "Mantissa has too many bits"));
+    }
+    let fraction = fraction >> extra_bits;
+
+    let bias = format.bias();
+    let biased = match exponent.checked_add(bias) {
+        Some(e) => e,
+        None    => return Err(SizedError::parse(s, "Not a valid hex float")),
+    };
+    let normal = biased > 0 && (biased as u128) < max_exponent;
+    let exponent = match (leading, fraction, exponent) {
+        // Zero
+        ("0", 0, 0) => 0,
+
+        // Subnormal
+        ("0", _, e) if e == 1 - bias => 0,
+
+        // Unnormal (only possible with an explicit integer bit)
+        ("0", _, _) if format.explicit_integer && normal => biased as u128,
+
+        // Normal
+        ("1", _, _) if normal => biased as u128,
+
+        _ => return Err(SizedError::parse(s, "Not a valid hex float")),
+    };
+
+    Ok(format.assemble(negative, exponent, leading == "1", fraction))
+}
+
+/// An internal function to get a mask of the lowest `bits` bits.
+fn bit_mask(bits: u32) -> u128 {
+    u128::MAX.checked_shr(128 - bits).unwrap_or(0)
+}
+
+/// An internal function to make sure a bitfield fits in its container.
+fn check_bits(container: u8, offset: u8, width: u8) -> SizedResult<()> {
+    context::check_width(container)?;
+
+    if width == 0 || offset as u32 + width as u32 > container as u32 * 8 {
+        return Err(SizedError::InvalidArgument(format!("Bitfield doesn't fit in a {}-byte container: {} bits at offset {}", container, width, offset)));
+    }
+
+    Ok(())
+}
+
+impl SizedDefinition {
+    /// Returns the size, in bytes, of the value at the context.
+    ///
+    /// For most types, this is always [`SizedDefinition::fixed_size`], and
+    /// the context isn't read. Variable-length integers are decoded to find
+    /// their length, so this can fail:
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\xe5\x8e\x26\x00".to_vec();
+    /// let context = Context::new(&buffer);
+    ///
+    /// assert_eq!(4, SizedDefinition::U32(Endian::Big).size(context).unwrap());
+    /// assert_eq!(3, SizedDefinition::ULEB128.size(context).unwrap());
+    /// assert_eq!(1, SizedDefinition::ULEB128.size(context.at(3)).unwrap());
+    /// assert!(SizedDefinition::ULEB128.size(context.at(4)).is_err());
+    /// ```
+    pub fn size(self, context: Context) -> SizedResult<u64> {
+        match self.fixed_size() {
+            Some(size) => Ok(size),
+            None       => Ok(self.read_sized(context)?.0 as u64),
+        }
+    }
+
+    /// Returns the size, in bytes, of the current type, or `None` if the
+    /// size depends on the data (see [`SizedDefinition::size`]).
+    pub fn fixed_size(self) -> Option<u64> {
+        Some(match self {
+            Self::U8      => 1,
+            Self::U16(_)  => 2,
+            Self::U24(_)  => 3,
+            Self::U32(_)  => 4,
+            Self::U64(_)  => 8,
+            Self::U128(_) => 16,
+            Self::UN(bytes, _) => bytes as u64,
+
+            Self::I8      => 1,
+            Self::I16(_)  => 2,
+            Self::I24(_)  => 3,
+            Self::I32(_)  => 4,
+            Self::I64(_)  => 8,
+            Self::I128(_) => 16,
+            Self::IN(bytes, _) => bytes as u64,
+            Self::Bits { container, .. } => container as u64,
+
+            Self::F16(_)  => 2,
+            Self::BF16(_) => 2,
+            Self::F32(_)  => 4,
+            Self::F64(_)  => 8,
+            Self::F80(_)  => 10,
+            Self::F128(_) => 16,
+
+            Self::ULEB128   => return None,
+            Self::SLEB128   => return None,
+            Self::ZigZag    => return None,
+            Self::GitVarint => return None,
+        })
+    }
+
+    /// An internal function to read a value along with its size, in bytes.
+    pub(crate) fn read_sized(self, context: Context) -> SizedResult<(usize, SizedValue)> {
+        match self {
+            Self::ULEB128   => context.read_uleb128().map(|(size, v)| (size, SizedValue::U64(v))),
+            Self::SLEB128   => context.read_sleb128().map(|(size, v)| (size, SizedValue::I64(v))),
+            Self::ZigZag    => context.read_zigzag().map(|(size, v)| (size, SizedValue::I64(v))),
+            Self::GitVarint => context.read_git_varint().map(|(size, v)| (size, SizedValue::U64(v))),
+
+            // Everything else has a fixed size
+            _ => Ok((self.fixed_size().unwrap_or_default() as usize, self.read(context)?)),
+        }
+    }
+
+    /// Read the value from the context, based on the [`SizedDefinition`].
+    ///
+    /// The [`SizedValue`] variant matches the [`SizedDefinition`] variant,
+    /// except that variable-length integers read as [`SizedValue::U64`] or
+    /// [`SizedValue::I64`].
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\xff\xff".to_vec();
+    /// let context = Context::new_at(&buffer, 0);
+    ///
+    /// assert_eq!(SizedValue::U16(65535), SizedDefinition::U16(Endian::Big).read(context).unwrap());
+    /// assert_eq!(SizedValue::I8(-1),     SizedDefinition::I8.read(context).unwrap());
+    /// ```
+    pub fn read(self, context: Context) -> SizedResult<SizedValue> {
+        match self {
+            Self::U8           => Ok(SizedValue::U8(context.read_u8()?)),
+            Self::U16(endian)  => Ok(SizedValue::U16(context.read_u16(endian)?)),
+            Self::U24(endian)  => Ok(SizedValue::U24(context.read_u24(endian)?)),
+            Self::U32(endian)  => Ok(SizedValue::U32(context.read_u32(endian)?)),
+            Self::U64(endian)  => Ok(SizedValue::U64(context.read_u64(endian)?)),
+            Self::U128(endian) => Ok(SizedValue::U128(context.read_u128(endian)?)),
+            Self::UN(bytes, endian) => Ok(SizedValue::UN(bytes, context.read_uint(bytes, endian)?)),
+
+            Self::I8           => Ok(SizedValue::I8(context.read_i8()?)),
+            Self::I16(endian)  => Ok(SizedValue::I16(context.read_i16(endian)?)),
+            Self::I24(endian)  => Ok(SizedValue::I24(context.read_i24(endian)?)),
+            Self::I32(endian)  => Ok(SizedValue::I32(context.read_i32(endian)?)),
+            Self::I64(endian)  => Ok(SizedValue::I64(context.read_i64(endian)?)),
+            Self::I128(endian) => Ok(SizedValue::I128(context.read_i128(endian)?)),
+            Self::IN(bytes, endian) => Ok(SizedValue::IN(bytes, context.read_int(bytes, endian)?)),
+            Self::Bits { container, endian, offset, width, signed } => {
+                check_bits(container, offset, width)?;
+
+                Ok(SizedValue::Bits {
+                    value: (context.read_uint(container, endian)? >> offset) & bit_mask(width as u32),
+                    container,
+                    offset,
+                    width,
+                    signed,
+                })
+            },
+
+            Self::F16(endian)  => Ok(SizedValue::F16(context.read_f16(endian)?)),
+            Self::BF16(endian) => Ok(SizedValue::BF16(context.read_bf16(endian)?)),
+            Self::F32(endian)  => Ok(SizedValue::F32(context.read_f32(endian)?)),
+            Self::F64(endian)  => Ok(SizedValue::F64(context.read_f64(endian)?)),
+            Self::F80(endian)  => Ok(SizedValue::F80(context.read_f80(endian)?)),
+            Self::F128(endian) => Ok(SizedValue::F128(context.read_f128(endian)?)),
+
+            Self::ULEB128 | Self::SLEB128 | Self::ZigZag | Self::GitVarint => Ok(self.read_sized(context)?.1),
+        }
+    }
+
+    /// Read `count` consecutive values, starting at the context, into a
+    /// [`SizedArray`].
+    ///
+    /// Fixed-size types are decoded in one pass over one slice, which is
+    /// much faster than calling [`SizedDefinition::read`] in a loop. Either
+    /// every value is read, or an error is returned.
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x01\x00\x00\x00\x02\x00\x00\x00\x03\x00\x00\x00".to_vec();
+    /// let context = Context::new(&buffer);
+    ///
+    /// let array = SizedDefinition::U32(Endian::Little).read_array(context, 3).unwrap();
+    /// assert_eq!(3, array.len());
+    /// assert_eq!(Some(SizedValue::U32(2)), array.get(1));
+    ///
+    /// if let SizedArray::U32(v) = array {
+    ///     assert_eq!(vec![1, 2, 3], v);
+    /// }
+    ///
+    /// assert!(SizedDefinition::U32(Endian::Little).read_array(context, 4).is_err());
+    /// ```
+    pub fn read_array(self, context: Context, count: usize) -> SizedResult<SizedArray> {
+        // Anything without a fast path is read from its own chunk
+        fn each<T>(context: Context, size: u64, count: usize, read: impl Fn(Context) -> SizedResult<T>) -> SizedResult<Vec<T>> {
+            context.chunks(size as usize, count)?.map(read).collect()
+        }
+
+        // Variable-length integers have to be read one after another
+        let mut next = context;
+
+        match self {
+            Self::U8           => Ok(SizedArray::U8(context.read_u8_array(count)?)),
+            Self::U16(endian)  => Ok(SizedArray::U16(context.read_u16_array(endian, count)?)),
+            Self::U24(endian)  => Ok(SizedArray::U24(each(context, 3, count, |c| c.read_u24(endian))?)),
+            Self::U32(endian)  => Ok(SizedArray::U32(context.read_u32_array(endian, count)?)),
+            Self::U64(endian)  => Ok(SizedArray::U64(context.read_u64_array(endian, count)?)),
+            Self::U128(endian) => Ok(SizedArray::U128(context.read_u128_array(endian, count)?)),
+            Self::UN(bytes, endian) => {
+                context::check_width(bytes)?;
+                Ok(SizedArray::UN(bytes, each(context, bytes as u64, count, |c| c.read_uint(bytes, endian))?))
+            },
+
+            Self::I8           => Ok(SizedArray::I8(context.read_i8_array(count)?)),
+            Self::I16(endian)  => Ok(SizedArray::I16(context.read_i16_array(endian, count)?)),
+            Self::I24(endian)  => Ok(SizedArray::I24(each(context, 3, count, |c| c.read_i24(endian))?)),
+            Self::I32(endian)  => Ok(SizedArray::I32(context.read_i32_array(endian, count)?)),
+            Self::I64(endian)  => Ok(SizedArray::I64(context.read_i64_array(endian, count)?)),
+            Self::I128(endian) => Ok(SizedArray::I128(context.read_i128_array(endian, count)?)),
+            Self::IN(bytes, endian) => {
+                context::check_width(bytes)?;
+                Ok(SizedArray::IN(bytes, each(context, bytes as u64, count, |c| c.read_int(bytes, endian))?))
+            },
+            Self::Bits { container, endian, offset, width, signed } => {
+                check_bits(container, offset, width)?;
+
+                Ok(SizedArray::Bits {
+                    values: each(context, container as u64, count, |c| Ok((c.read_uint(container, endian)? >> offset) & bit_mask(width as u32)))?,
+                    container,
+                    offset,
+                    width,
+                    signed,
+                })
+            },
+
+            Self::F16(endian)  => Ok(SizedArray::F16(context.read_u16_array(endian, count)?.into_iter().map(f16::from_bits).collect())),
+            Self::BF16(endian) => Ok(SizedArray::BF16(context.read_u16_array(endian, count)?.into_iter().map(bf16::from_bits).collect())),
+            Self::F32(endian)  => Ok(SizedArray::F32(context.read_f32_array(endian, count)?)),
+            Self::F64(endian)  => Ok(SizedArray::F64(context.read_f64_array(endian, count)?)),
+            Self::F80(endian)  => Ok(SizedArray::F80(each(context, 10, count, |c| c.read_f80(endian))?)),
+            Self::F128(endian) => Ok(SizedArray::F128(context.read_u128_array(endian, count)?.into_iter().map(Float128::from_bits).collect())),
+
+            Self::ULEB128 | Self::GitVarint => {
+                Ok(SizedArray::U64((0..count).map(|_| u64::try_from(next.read_next(self)?)).collect::<SizedResult<_>>()?))
+            },
+            Self::SLEB128 | Self::ZigZag => {
+                Ok(SizedArray::I64((0..count).map(|_| i64::try_from(next.read_next(self)?)).collect::<SizedResult<_>>()?))
+            },
+        }
+    }
+
+    /// Read data from the context, based on the [`SizedDefinition`], and
+    /// display it based on the `SizedDisplay`
+    ///
+    /// This hides [`ToString::to_string`], so `d.to_string()` won't compile;
+    /// use `format!("{}", d)` to get the definition's type name instead:
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x01\x02".to_vec();
+    /// let d = SizedDefinition::U16(Endian::Little);
+    ///
+    /// assert_eq!("513", d.to_string(Context::new(&buffer), SizedDisplay::Decimal).unwrap());
+    /// assert_eq!("u16le", format!("{}", d));
+    /// ```
+    pub fn to_string(self, context: Context, display: SizedDisplay) -> SizedResult<String> {
+        self.read(context)?.format(display)
+    }
+
+    /// Read data from the context, based on the [`SizedDefinition`], and
+    /// write it to `out` based on the `SizedDisplay`.
+    ///
+    /// This is the same as [`SizedDefinition::to_string`], but it doesn't
+    /// allocate (see [`SizedValue::write_to`]), so it's much faster for
+    /// displaying lots of values - for example, into a reused [`String`]:
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x00\x01\x00\x02\x00\x03".to_vec();
+    /// let context = Context::new(&buffer);
+    /// let d = SizedDefinition::U16(Endian::Big);
+    ///
+    /// let mut line = String::with_capacity(64);
+    /// for i in 0..3 {
+    ///     d.write_to(context.at(i * 2), SizedDisplay::Hex(Default::default()), &mut line).unwrap();
+    ///     line.push(' ');
+    /// }
+    ///
+    /// assert_eq!("0x0001 0x0002 0x0003 ", line);
+    /// ```
+    pub fn write_to<W: fmt::Write>(self, context: Context, display: SizedDisplay, out: &mut W) -> SizedResult<()> {
+        self.read(context)?.write_to(display, out)
+    }
+
+    /// Read `count` consecutive values, starting at the context, and display
+    /// them as a list based on the `SizedDisplay` and [`ArrayOptions`].
+    ///
+    /// Variable-length types are read one after another, so every value is
+    /// where the previous one ends. Values that are cut off by
+    /// [`ArrayOptions::max_elements`] aren't read at all.
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x00\x01\x00\x02\x00\x03\x00\x04\x00\x05".to_vec();
+    /// let context = Context::new(&buffer);
+    /// let d = SizedDefinition::U16(Endian::Big);
+    /// let hex = SizedDisplay::Hex(Default::default());
+    ///
+    /// assert_eq!("[0x0001, 0x0002, 0x0003]", d.to_string_array(context, 3, hex, &Default::default()).unwrap());
+    ///
+    /// let options = ArrayOptions {
+    ///     per_line: Some(2),
+    ///     max_elements: Some(4),
+    ///     offsets: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     "00000000: [0x0001, 0x0002,\n00000004:  0x0003, 0x0004,\n00000008:  … 1 more]",
+    ///     d.to_string_array(context, 5, hex, &options).unwrap(),
+    /// );
+    /// ```
+    pub fn to_string_array(self, context: Context, count: usize, display: SizedDisplay, options: &ArrayOptions) -> SizedResult<String> {
+        let shown = options.max_elements.map_or(count, |max| max.min(count));
+        let per_line = options.per_line.filter(|&n| n > 0).unwrap_or(usize::MAX);
+        let (open, indent, close) = match options.brackets {
+            true  => ("[", " ", "]"),
+            false => ("", "", ""),
+        };
+
+        let mut s = String::new();
+        if options.offsets {
+            write!(s, "{:08x}: ", context.position())?;
+        }
+        s.push_str(open);
+
+        // The "more" message goes where the next value would have
+        let mut next = context;
+        for i in 0..(shown + (shown < count) as usize) {
+            if i > 0 && i % per_line == 0 {
+                s.push_str(options.separator.trim_end());
+                s.push('\n');
+
+                if options.offsets {
+                    write!(s, "{:08x}: ", next.position())?;
+                }
+                s.push_str(indent);
+            } else if i > 0 {
+                s.push_str(&options.separator);
+            }
+
+            match i < shown {
+                true  => next.read_next(self)?.write_to(display, &mut s)?,
+                false => write!(s, "… {} more", count - shown)?,
+            };
+        }
+        s.push_str(close);
+
+        Ok(s)
+    }
+
+    /// Parse a string, in the format that `display` would produce, back into
+    /// the bytes that [`SizedDefinition`] represents.
+    ///
+    /// This is the inverse of [`SizedDefinition::to_string`] - prefixes must
+    /// be present (or not) to match the options, hex digits can be either
+    /// case, and leading zeroes are optional. The value must fit in the
+    /// type, and the bytes are returned in the proper endian:
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let d = SizedDefinition::U32(Endian::Little);
+    ///
+    /// assert_eq!(b"\x44\x43\x42\x41".to_vec(), d.from_string("0x41424344", SizedDisplay::Hex(Default::default())).unwrap());
+    /// assert_eq!(b"\x44\x43\x42\x41".to_vec(), d.from_string("1094861636", SizedDisplay::Decimal).unwrap());
+    /// assert!(d.from_string("4294967296", SizedDisplay::Decimal).is_err());
+    /// ```
+    pub fn from_string(self, s: &str, display: SizedDisplay) -> SizedResult<Vec<u8>> {
+        if let SizedDisplay::FloatParts = display {
+            return Err(SizedError::UnsupportedDisplay { definition: format!("{}", self), display });
+        }
+
+        // Variable-length integers are written to a buffer that's big enough
+        // for anything, then trimmed
+        let mut v = vec![0; self.fixed_size().unwrap_or(context::MAX_VARINT_BYTES as u64) as usize];
+        let mut context = ContextMut::new(&mut v);
+
+        match self {
+            Self::U8             => context.write_u8(parse_unsigned(s, display, 8)? as u8)?,
+            Self::U16(endian)    => context.write_u16(parse_unsigned(s, display, 16)? as u16, endian)?,
+            Self::U24(endian)    => context.write_u24(parse_unsigned(s, display, 24)? as u32, endian)?,
+            Self::U32(endian)    => context.write_u32(parse_unsigned(s, display, 32)? as u32, endian)?,
+            Self::U64(endian)    => context.write_u64(parse_unsigned(s, display, 64)? as u64, endian)?,
+            Self::U128(endian)   => context.write_u128(parse_unsigned(s, display, 128)?, endian)?,
+            Self::UN(bytes, endian) => {
+                context::check_width(bytes)?;
+                context.write_uint(parse_unsigned(s, display, bytes as u32 * 8)?, bytes, endian)?
+            },
+
+            Self::I8             => context.write_i8(parse_signed(s, display, 8)? as i8)?,
+            Self::I16(endian)    => context.write_i16(parse_signed(s, display, 16)? as i16, endian)?,
+            Self::I24(endian)    => context.write_i24(parse_signed(s, display, 24)? as i32, endian)?,
+            Self::I32(endian)    => context.write_i32(parse_signed(s, display, 32)? as i32, endian)?,
+            Self::I64(endian)    => context.write_i64(parse_signed(s, display, 64)? as i64, endian)?,
+            Self::I128(endian)   => context.write_i128(parse_signed(s, display, 128)?, endian)?,
+            Self::IN(bytes, endian) => {
+                context::check_width(bytes)?;
+                context.write_int(parse_signed(s, display, bytes as u32 * 8)?, bytes, endian)?
+            },
+            Self::Bits { container, endian, offset, width, signed } => {
+                check_bits(container, offset, width)?;
+
+                let value = match (display, signed) {
+                    (SizedDisplay::Binary(options), _) if options.in_place => parse_binary_in_place(s, container as u32 * 8, offset as u32, width as u32, options)?,
+                    (_, false) => parse_unsigned(s, display, width as u32)?,
+                    (_, true)  => parse_signed(s, display, width as u32)? as u128 & bit_mask(width as u32),
+                };
+
+                context.write_uint(value << offset, container, endian)?
+            },
+
+            Self::F16(endian)    => context.write_u16(parse_float(s, display, FloatFormat::F16, |v: f16| v.to_bits() as u128)? as u16, endian)?,
+            Self::BF16(endian)   => context.write_u16(parse_float(s, display, FloatFormat::BF16, |v: bf16| v.to_bits() as u128)? as u16, endian)?,
+            Self::F32(endian)    => context.write_u32(parse_float(s, display, FloatFormat::F32, |v: f32| v.to_bits() as u128)? as u32, endian)?,
+            Self::F64(endian)    => context.write_u64(parse_float(s, display, FloatFormat::F64, |v: f64| v.to_bits() as u128)? as u64, endian)?,
+            Self::F80(endian)    => context.write_f80(Float80::from_bits(parse_float(s, display, FloatFormat::F80, Float80::to_bits)?), endian)?,
+            Self::F128(endian)   => context.write_f128(Float128::from_bits(parse_float(s, display, FloatFormat::F128, Float128::to_bits)?), endian)?,
+
+            Self::ULEB128        => { let size = context.write_uleb128(parse_unsigned(s, display, 64)? as u64)?; v.truncate(size) },
+            Self::SLEB128        => { let size = context.write_sleb128(parse_signed(s, display, 64)? as i64)?; v.truncate(size) },
+            Self::ZigZag         => { let size = context.write_zigzag(parse_signed(s, display, 64)? as i64)?; v.truncate(size) },
+            Self::GitVarint      => { let size = context.write_git_varint(parse_unsigned(s, display, 64)? as u64)?; v.truncate(size) },
+        };
+
+        Ok(v)
+    }
+
+    /// Returns the same type with a different endian, or `None` for types
+    /// that don't have one (like [`SizedDefinition::U8`]).
+    pub fn with_endian(self, endian: Endian) -> Option<Self> {
+        match self {
+            Self::U16(_)  => Some(Self::U16(endian)),
+            Self::U24(_)  => Some(Self::U24(endian)),
+            Self::U32(_)  => Some(Self::U32(endian)),
+            Self::U64(_)  => Some(Self::U64(endian)),
+            Self::U128(_) => Some(Self::U128(endian)),
+            Self::UN(bytes, _) => Some(Self::UN(bytes, endian)),
+
+            Self::I16(_)  => Some(Self::I16(endian)),
+            Self::I24(_)  => Some(Self::I24(endian)),
+            Self::I32(_)  => Some(Self::I32(endian)),
+            Self::I64(_)  => Some(Self::I64(endian)),
+            Self::I128(_) => Some(Self::I128(endian)),
+            Self::IN(bytes, _) => Some(Self::IN(bytes, endian)),
+            Self::Bits { container, offset, width, signed, .. } => Some(Self::Bits { container, endian, offset, width, signed }),
+
+            Self::F16(_)  => Some(Self::F16(endian)),
+            Self::BF16(_) => Some(Self::BF16(endian)),
+            Self::F32(_)  => Some(Self::F32(endian)),
+            Self::F64(_)  => Some(Self::F64(endian)),
+            Self::F80(_)  => Some(Self::F80(endian)),
+            Self::F128(_) => Some(Self::F128(endian)),
+
+            Self::U8 | Self::I8 | Self::ULEB128 | Self::SLEB128 | Self::ZigZag | Self::GitVarint => None,
+        }
+    }
+
+    /// Guess whether `count` consecutive values of this type, starting at
+    /// the context, are more likely big or little endian. The definition's
+    /// own endian is ignored.
+    ///
+    /// Each value is read both ways and scored on how plausible it looks -
+    /// small integers, aligned pointers, and floats in a sane range win. The
+    /// higher total is returned, or `None` if it's a tie (for example, if
+    /// the data is all zeroes). This is a heuristic, so more values give a
+    /// better guess.
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let buffer = b"\x01\x00\x00\x00\x20\x00\x00\x00\xff\xff\xff\xff".to_vec();
+    /// let context = Context::new(&buffer);
+    ///
+    /// assert_eq!(Some(Endian::Little), SizedDefinition::I32(Endian::Big).guess_endian(context, 3).unwrap());
+    /// ```
+    pub fn guess_endian(self, context: Context, count: u64) -> SizedResult<Option<Endian>> {
+        let (size, big, little) = match (self.fixed_size(), self.with_endian(Endian::Big), self.with_endian(Endian::Little)) {
+            (Some(size), Some(big), Some(little)) => (size, big, little),
+            _ => return Err(SizedError::InvalidArgument(format!("Can't guess the endian of {}", self))),
+        };
+
+        let mut big_score: u64 = 0;
+        let mut little_score: u64 = 0;
+        for i in 0..count {
+            let context = context.at(context.position() + i * size);
+
+            big_score += big.read(context)?.plausibility() as u64;
+            little_score += little.read(context)?.plausibility() as u64;
+        }
+
+        Ok(match big_score.cmp(&little_score) {
+            std::cmp::Ordering::Greater => Some(Endian::Big),
+            std::cmp::Ordering::Less    => Some(Endian::Little),
+            std::cmp::Ordering::Equal   => None,
+        })
+    }
+
+    /// Returns `true` for variants that can succeed in [`to_u64`]
+    pub fn can_be_u64(self) -> bool {
+        match self {
+            Self::U8      => true,
+            Self::U16(_)  => true,
+            Self::U24(_)  => true,
             Self::U32(_)  => true,
             Self::U64(_)  => true,
             Self::U128(_) => false,
+            Self::UN(bytes, _) => (1..=8).contains(&bytes),
 
             Self::I8      => false,
             Self::I16(_)  => false,
+            Self::I24(_)  => false,
             Self::I32(_)  => false,
             Self::I64(_)  => false,
             Self::I128(_) => false,
+            Self::IN(_, _) => false,
+            Self::Bits { width, signed, .. } => !signed && width <= 64,
 
+            Self::F16(_)  => false,
+            Self::BF16(_) => false,
             Self::F32(_)  => false,
             Self::F64(_)  => false,
+            Self::F80(_)  => false,
+            Self::F128(_) => false,
+
+            Self::ULEB128   => true,
+            Self::SLEB128   => false,
+            Self::ZigZag    => false,
+            Self::GitVarint => true,
         }
     }
 
     /// Returns `true` for variants that can succeed in [`to_i64`]
     pub fn can_be_i64(self) -> bool {
         match self {
-            Self::U8      => false,
-            Self::U16(_)  => false,
-            Self::U32(_)  => false,
+            Self::U8      => true,
+            Self::U16(_)  => true,
+            Self::U24(_)  => true,
+            Self::U32(_)  => true,
             Self::U64(_)  => false,
             Self::U128(_) => false,
+            Self::UN(bytes, _) => (1..=7).contains(&bytes),
 
             Self::I8      => true,
             Self::I16(_)  => true,
+            Self::I24(_)  => true,
             Self::I32(_)  => true,
             Self::I64(_)  => true,
             Self::I128(_) => false,
+            Self::IN(bytes, _) => (1..=8).contains(&bytes),
+            Self::Bits { width, signed, .. } => width < 64 || (signed && width == 64),
 
+            Self::F16(_)  => false,
+            Self::BF16(_) => false,
             Self::F32(_)  => false,
             Self::F64(_)  => false,
+            Self::F80(_)  => false,
+            Self::F128(_) => false,
+
+            Self::ULEB128   => false,
+            Self::SLEB128   => true,
+            Self::ZigZag    => true,
+            Self::GitVarint => false,
         }
     }
 
@@ -659,25 +1690,8 @@ impl SizedDefinition {
     /// Only unsigned values of 64-bits or less can be converted to a [`u64`].
     /// Everything else will return an error - we don't typecast signed to
     /// unsigned.
-    pub fn to_u64(self, context: Context) -> SimpleResult<u64> {
-        match self {
-            Self::U8          => Ok(context.read_u8()? as u64),
-            Self::U16(endian) => Ok(context.read_u16(endian)? as u64),
-            Self::U32(endian) => Ok(context.read_u32(endian)? as u64),
-            Self::U64(endian) => Ok(context.read_u64(endian)? as u64),
-
-            // None of these can become u32
-            Self::U128(_) => bail!("Can't convert u128 into u64"),
-
-            Self::I8      => bail!("Can't convert i8 (signed) into u64"),
-            Self::I16(_)  => bail!("Can't convert i16 (signed) into u64"),
-            Self::I32(_)  => bail!("Can't convert i32 (signed) into u64"),
-            Self::I64(_)  => bail!("Can't convert i64 (signed) into u64"),
-            Self::I128(_) => bail!("Can't convert i128 (signed) into u64"),
-
-            Self::F32(_)  => bail!("Can't convert floating point into u64"),
-            Self::F64(_)  => bail!("Can't convert floating point into u64"),
-        }
+    pub fn to_u64(self, context: Context) -> SizedResult<u64> {
+        u64::try_from(self.read(context)?)
     }
 
     /// Convert to a signed 64-bit value, if possible.
@@ -686,50 +1700,226 @@ impl SizedDefinition {
     /// `SizedDefinition::I8` with a value of `FF` will convert to the [`i64`]
     /// value `-1`, or `0xFFFFFFFFFFFFFFFF`.
     ///
-    /// Only signed values of 64-bits or less can be converted to an [`i64`].
-    /// Everything else will return an error - we don't typecast unsigned to
-    /// signed.
-    pub fn to_i64(self, context: Context) -> SimpleResult<i64> {
-        match self {
-            // Don't let unsigned values become signed
-            Self::U8      => bail!("Can't convert i8 (signed) into i64"),
-            Self::U16(_)  => bail!("Can't convert i16 (signed) into i64"),
-            Self::U32(_)  => bail!("Can't convert i32 (signed) into i64"),
-            Self::U64(_)  => bail!("Can't convert i64 (signed) into i64"),
-            Self::U128(_) => bail!("Can't convert i128 (signed) into i64"),
-
-            Self::I8 =>          Ok(context.read_i8()? as i64),
-            Self::I16(endian) => Ok(context.read_i16(endian)? as i64),
-            Self::I32(endian) => Ok(context.read_i32(endian)? as i64),
-            Self::I64(endian) => Ok(context.read_i64(endian)? as i64),
+    /// Only signed values of 64-bits or less, and unsigned values of 63-bits
+    /// or less, can be converted to an [`i64`]. Everything else will return
+    /// an error - we don't typecast unsigned values that might not fit.
+    pub fn to_i64(self, context: Context) -> SizedResult<i64> {
+        i64::try_from(self.read(context)?)
+    }
+}
 
-            // 128 bit can't go into 64 bit
-            Self::I128(_) => bail!("Can't convert u128 into i64"),
+impl fmt::Display for SizedDefinition {
+    /// Display the definition as a compact type name, like `u8` or `u32le`.
+    ///
+    /// The output can be parsed back with [`str::parse`]. Note that
+    /// [`SizedDefinition::to_string`] reads and formats a value, so use
+    /// `format!("{}", definition)` to get the name.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U8           => write!(f, "u8"),
+            Self::U16(endian)  => write!(f, "u16{}", endian),
+            Self::U24(endian)  => write!(f, "u24{}", endian),
+            Self::U32(endian)  => write!(f, "u32{}", endian),
+            Self::U64(endian)  => write!(f, "u64{}", endian),
+            Self::U128(endian) => write!(f, "u128{}", endian),
+            Self::UN(bytes, endian) => write!(f, "u{}{}", *bytes as u32 * 8, endian),
+
+            Self::I8           => write!(f, "i8"),
+            Self::I16(endian)  => write!(f, "i16{}", endian),
+            Self::I24(endian)  => write!(f, "i24{}", endian),
+            Self::I32(endian)  => write!(f, "i32{}", endian),
+            Self::I64(endian)  => write!(f, "i64{}", endian),
+            Self::I128(endian) => write!(f, "i128{}", endian),
+            Self::IN(bytes, endian) => write!(f, "i{}{}", *bytes as u32 * 8, endian),
+            Self::Bits { container, endian, offset, width, signed } => {
+                write!(f, "{}{}{}[{}..{}]",
+                    if *signed { "i" } else { "u" },
+                    *container as u32 * 8,
+                    endian,
+                    offset,
+                    *offset as u32 + *width as u32,
+                )
+            },
 
-            // Float certainly can't
-            Self::F32(_)  => bail!("Can't convert floating point into i64"),
-            Self::F64(_)  => bail!("Can't convert floating point into i64"),
+            Self::F16(endian)  => write!(f, "f16{}", endian),
+            Self::BF16(endian) => write!(f, "bf16{}", endian),
+            Self::F32(endian)  => write!(f, "f32{}", endian),
+            Self::F64(endian)  => write!(f, "f64{}", endian),
+            Self::F80(endian)  => write!(f, "f80{}", endian),
+            Self::F128(endian) => write!(f, "f128{}", endian),
+
+            Self::ULEB128   => write!(f, "uleb128"),
+            Self::SLEB128   => write!(f, "sleb128"),
+            Self::ZigZag    => write!(f, "zigzag"),
+            Self::GitVarint => write!(f, "git_varint"),
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// An internal type for looking up type names - some types need an endian,
+/// and some don't.
+enum TypeName {
+    Single(SizedDefinition),
+    Multi(fn(Endian) -> SizedDefinition),
 
-    use pretty_assertions::assert_eq;
-    use simple_error::SimpleResult;
+    /// An arbitrary width: whether it's signed, and how many bytes
+    Width(bool, u8),
+}
 
-    #[test]
-    fn test_hex_u8() -> SimpleResult<()> {
-        let data = b"\x00\x7F\x80\xFF".to_vec();
+/// An internal function to look up a type name (without its endian suffix).
+fn type_name(name: &str) -> Option<TypeName> {
+    match name {
+        "u8"   | "uint8"   | "uint8_t"   | "byte" | "uchar"  => Some(TypeName::Single(SizedDefinition::U8)),
+        "u16"  | "uint16"  | "uint16_t"  | "word" | "ushort" => Some(TypeName::Multi(SizedDefinition::U16)),
+        "u24"  | "uint24"  | "uint24_t"                      => Some(TypeName::Multi(SizedDefinition::U24)),
+        "u32"  | "uint32"  | "uint32_t"  | "dword"           => Some(TypeName::Multi(SizedDefinition::U32)),
+        "u64"  | "uint64"  | "uint64_t"  | "qword"           => Some(TypeName::Multi(SizedDefinition::U64)),
+        "u128" | "uint128" | "uint128_t"                     => Some(TypeName::Multi(SizedDefinition::U128)),
+
+        "i8"   | "int8"    | "int8_t"    | "sbyte"           => Some(TypeName::Single(SizedDefinition::I8)),
+        "i16"  | "int16"   | "int16_t"   | "short"           => Some(TypeName::Multi(SizedDefinition::I16)),
+        "i24"  | "int24"   | "int24_t"                       => Some(TypeName::Multi(SizedDefinition::I24)),
+        "i32"  | "int32"   | "int32_t"                     => Some(TypeName::Multi(SizedDefinition::I32)),
+        "i64"  | "int64"   | "int64_t"                       => Some(TypeName::Multi(SizedDefinition::I64)),
+        "i128" | "int128"  | "int128_t"                      => Some(TypeName::Multi(SizedDefinition::I128)),
+
+        "f16"  | "half"    | "binary16"  | "float16"         => Some(TypeName::Multi(SizedDefinition::F16)),
+        "bf16" | "bfloat16"                                  => Some(TypeName::Multi(SizedDefinition::BF16)),
+        "f32"  | "float"   | "single"                        => Some(TypeName::Multi(SizedDefinition::F32)),
+        "f64"  | "double"                                    => Some(TypeName::Multi(SizedDefinition::F64)),
+        "f80"  | "extended" | "float80"                      => Some(TypeName::Multi(SizedDefinition::F80)),
+        "f128" | "quad"    | "binary128" | "float128"        => Some(TypeName::Multi(SizedDefinition::F128)),
+
+        "uleb128" | "leb128" | "varint" | "uvarint"          => Some(TypeName::Single(SizedDefinition::ULEB128)),
+        "sleb128"                                            => Some(TypeName::Single(SizedDefinition::SLEB128)),
+        "zigzag" | "svarint" | "sint64" | "sint32"           => Some(TypeName::Single(SizedDefinition::ZigZag)),
+        "git_varint" | "gitvarint"                           => Some(TypeName::Single(SizedDefinition::GitVarint)),
+
+        // Any other whole number of bytes, like u48 or int40_t
+        _ => {
+            let name = name.strip_suffix("_t").unwrap_or(name);
+            let (signed, bits) = match name.strip_prefix("uint").or_else(|| name.strip_prefix('u')) {
+                Some(bits) => (false, bits),
+                None       => (true, name.strip_prefix("int").or_else(|| name.strip_prefix('i'))?),
+            };
+
+            match bits.parse::<u8>() {
+                Ok(bits) if bits % 8 == 0 && (8..=128).contains(&bits) => Some(TypeName::Width(signed, bits / 8)),
+                _ => None,
+            }
+        },
+    }
+}
 
-        let tests = vec![
-            // index  uppercase   prefix   padded    expected
-            (   0,    false,      false,   false,    "0"),
-            (   0,    true,       false,   false,    "0"),
-            (   0,    false,      true,    false,    "0x0"),
-            (   0,    false,      false,   true,     "00"),
+/// The endian suffixes that type names can have, like the `le` in `u32le`.
+const ENDIAN_SUFFIXES: [(&str, Endian); 6] = [
+    ("le",   Endian::Little),
+    ("be",   Endian::Big),
+    ("lews", Endian::LittleWordSwapped),
+    ("bews", Endian::BigWordSwapped),
+    ("pdp",  Endian::LittleWordSwapped),
+    ("ne",   Endian::Native),
+];
+
+impl FromStr for SizedDefinition {
+    type Err = SizedError;
+
+    /// Parse a compact type name, like `u8`, `u32le`, or `f64be`.
+    ///
+    /// Common aliases such as `uint32_t`, `dword`, `qword`, and `double` also
+    /// work, and the endian suffix can be separated by `_` or `-` (`dword_le`).
+    /// Parsing is case insensitive. Multi-byte types must have an endian -
+    /// `le`, `be`, `ne` (native), or a word-swapped order (`lews`, `bews`, or
+    /// `pdp`).
+    ///
+    /// ```
+    /// use sized_number::{SizedDefinition, Endian};
+    ///
+    /// assert_eq!(SizedDefinition::U32(Endian::Little), "u32le".parse().unwrap());
+    /// assert_eq!(SizedDefinition::F64(Endian::Big),    "double_be".parse().unwrap());
+    /// assert_eq!(SizedDefinition::U32(Endian::LittleWordSwapped), "u32_pdp".parse().unwrap());
+    /// assert!("u32".parse::<SizedDefinition>().is_err());
+    /// ```
+    fn from_str(s: &str) -> SizedResult<Self> {
+        let name = s.trim().to_lowercase();
+
+        // Bitfields are a container followed by a range, like u16be[3..8]
+        if let Some(name) = name.strip_suffix(']') {
+            let (container, range) = match name.split_once('[') {
+                Some(parts) => parts,
+                None        => return Err(SizedError::parse(s, "Not a valid bitfield")),
+            };
+            let (start, end) = match range.split_once("..") {
+                Some((start, end)) => (start.parse::<u8>(), end.parse::<u8>()),
+                None               => return Err(SizedError::parse(s, "Not a valid bit range")),
+            };
+            let (offset, end) = match (start, end) {
+                (Ok(start), Ok(end)) if start < end => (start, end),
+                _ => return Err(SizedError::parse(s, "Not a valid bit range")),
+            };
+
+            // A single byte doesn't need an endian, but keep it if it's there
+            // so the definition displays the same way
+            let byte_endian = ENDIAN_SUFFIXES.iter()
+                .find(|(suffix, _)| container.ends_with(suffix))
+                .map_or(Endian::Big, |&(_, endian)| endian);
+
+            // The container's signedness is the field's signedness
+            let (container, endian, signed) = match container.parse()? {
+                Self::U8 => (1, byte_endian, false),
+                Self::I8 => (1, byte_endian, true),
+                d @ (Self::U16(e) | Self::U24(e) | Self::U32(e) | Self::U64(e) | Self::U128(e) | Self::UN(_, e)) => (d.fixed_size().unwrap_or_default() as u8, e, false),
+                d @ (Self::I16(e) | Self::I24(e) | Self::I32(e) | Self::I64(e) | Self::I128(e) | Self::IN(_, e)) => (d.fixed_size().unwrap_or_default() as u8, e, true),
+                _ => return Err(SizedError::parse(s, "Bitfields must be in an integer")),
+            };
+            check_bits(container, offset, end - offset)?;
+
+            return Ok(Self::Bits { container, endian, offset, width: end - offset, signed });
+        }
+
+        // Check the full name first, since some names (like "double") end with
+        // something that looks like an endian
+        match type_name(&name) {
+            Some(TypeName::Single(d)) => return Ok(d),
+            Some(TypeName::Multi(_)) | Some(TypeName::Width(_, _)) => {
+                return Err(SizedError::parse(s, format!("Missing endianness for multi-byte type (try {}le or {}be)", name, name)));
+            },
+            None                      => (),
+        };
+
+        for (suffix, endian) in &ENDIAN_SUFFIXES {
+            if let Some(base) = name.strip_suffix(suffix) {
+                match type_name(base.trim_end_matches(['_', '-'])) {
+                    Some(TypeName::Single(d)) => return Ok(d),
+                    Some(TypeName::Multi(d))  => return Ok(d(*endian)),
+                    Some(TypeName::Width(false, bytes)) => return Ok(SizedDefinition::UN(bytes, *endian)),
+                    Some(TypeName::Width(true, bytes))  => return Ok(SizedDefinition::IN(bytes, *endian)),
+                    None                      => (),
+                };
+            }
+        }
+
+        Err(SizedError::parse(s, "Unknown type"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::SimpleResult;
+
+    #[test]
+    fn test_hex_u8() -> SimpleResult<()> {
+        let data = b"\x00\x7F\x80\xFF".to_vec();
+
+        let tests = vec![
+            // index  uppercase   prefix   padded    expected
+            (   0,    false,      false,   false,    "0"),
+            (   0,    true,       false,   false,    "0"),
+            (   0,    false,      true,    false,    "0x0"),
+            (   0,    false,      false,   true,     "00"),
             (   0,    true,       true,    true,     "0x00"),
 
             // index  uppercase   prefix   padded    expected
@@ -763,9 +1953,10 @@ mod tests {
                 SizedDefinition::U8.to_string(
                     context,
                     SizedDisplay::Hex(HexOptions {
-                        uppercase: uppercase,
-                        prefix: prefix,
-                        padded: padded,
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: false,
                     })
                 )?
             );
@@ -801,17 +1992,659 @@ mod tests {
             (   4,    true,       true,    true,     "0xFFFF"),
         ];
 
-        for (index, uppercase, prefix, padded, expected) in tests {
+        for (index, uppercase, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U16(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: false,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_u32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  uppercase   prefix   padded    expected
+            (   0,    false,      false,   false,    "1234"),
+            (   0,    true,       false,   false,    "1234"),
+            (   0,    false,      true,    false,    "0x1234"),
+            (   0,    false,      false,   true,     "00001234"),
+            (   0,    true,       true,    true,     "0x00001234"),
+
+            // index  uppercase   prefix   padded    expected
+            (   4,    false,      false,   false,    "ffffffff"),
+            (   4,    true,       false,   false,    "FFFFFFFF"),
+            (   4,    false,      true,    false,    "0xffffffff"),
+            (   4,    false,      false,   true,     "ffffffff"),
+            (   4,    true,       true,    true,     "0xFFFFFFFF"),
+        ];
+
+        for (index, uppercase, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U32(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: false,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_u64_big_endian() -> SimpleResult<()> {
+        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  uppercase   prefix   padded    expected
+            (   0,    false,      false,   false,    "1234ffffffff"),
+            (   0,    true,       false,   false,    "1234FFFFFFFF"),
+            (   0,    false,      true,    false,    "0x1234ffffffff"),
+            (   0,    false,      false,   true,     "00001234ffffffff"),
+            (   0,    true,       true,    true,     "0x00001234FFFFFFFF"),
+        ];
+
+        for (index, uppercase, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U64(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: false,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_u64_little_endian() -> SimpleResult<()> {
+        let data = b"\x00\x12\x34\xFF\xFF\xFF\xFF\x00".to_vec();
+
+        let tests = vec![
+            // index  uppercase   prefix   padded    expected
+            (   0,    false,      false,   false,    "ffffffff341200"),
+            (   0,    true,       false,   false,    "FFFFFFFF341200"),
+            (   0,    false,      true,    false,    "0xffffffff341200"),
+            (   0,    false,      false,   true,     "00ffffffff341200"),
+            (   0,    true,       true,    true,     "0x00FFFFFFFF341200"),
+        ];
+
+        for (index, uppercase, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U64(Endian::Little).to_string(
+                    context,
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: false,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_u128_big_endian() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff".to_vec();
+
+        let tests = vec![
+            // index  uppercase   prefix   padded    expected
+            (   0,    false,      false,   false,    "1"),
+            (   0,    true,       false,   false,    "1"),
+            (   0,    false,      true,    false,    "0x1"),
+            (   0,    false,      false,   true,     "00000000000000000000000000000001"),
+            (   0,    true,       true,    true,     "0x00000000000000000000000000000001"),
+
+            // index  uppercase   prefix   padded    expected
+            (   16,    false,      false,   false,    "112233445566778899aabbccddeeff"),
+            (   16,    true,       false,   false,    "112233445566778899AABBCCDDEEFF"),
+            (   16,    false,      true,    false,    "0x112233445566778899aabbccddeeff"),
+            (   16,    false,      false,   true,     "00112233445566778899aabbccddeeff"),
+            (   16,    true,       true,    true,     "0x00112233445566778899AABBCCDDEEFF"),
+        ];
+
+        for (index, uppercase, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U128(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: false,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_u8() -> SimpleResult<()> {
+        let data = b"\x00\x7F\x80\xFF".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (   0,    "0"),
+            (   1,    "127"),
+            (   2,    "128"),
+            (   3,    "255"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U8.to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_i8() -> SimpleResult<()> {
+        let data = b"\x00\x7F\x80\xFF".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (   0,    "0"),
+            (   1,    "127"),
+            (   2,    "-128"),
+            (   3,    "-1"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::I8.to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_u16() -> SimpleResult<()> {
+        let data = b"\x00\xFF\x00\x01\x00\x00\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (   0,    "255"),
+            (   2,    "1"),
+            (   4,    "0"),
+            (   6,    "65535"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U16(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_u32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\xff\xff\xff\xff\x7f\xff\xff\xff\x80\x00\x00\x00".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (   0,    "0"),
+            (   4,    "4294967295"),
+            (   8,    "2147483647"),
+            (  12,    "2147483648"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U32(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_i32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\xff\xff\xff\xff\x7f\xff\xff\xff\x80\x00\x00\x00".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (   0,    "0"),
+            (   4,    "-1"),
+            (   8,    "2147483647"),
+            (  12,    "-2147483648"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::I32(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_i64() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x7f\xff\xff\xff\xff\xff\xff\xff\x80\x00\x00\x00\x00\x00\x00\x00\xff\xff\xff\xff\xff\xff\xff\xff".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (  0,    "0"),
+            (  8,    "9223372036854775807"),
+            (  16,   "-9223372036854775808"),
+            (  24,   "-1"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::I64(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_u128() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (  0,    "0"),
+            (  16,   "340282366920938463463374607431768211455"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U128(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_i128() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  expected
+            (  0,    "0"),
+            (  16,   "-1"),
+        ];
+
+        for (index, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::I128(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Decimal
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_octal_u8() -> SimpleResult<()> {
+        let data = b"\x00\x7F\x80\xFF".to_vec();
+
+        let tests = vec![
+            // index  prefix   padded   expected
+            (   0,    false,   false,   "0"),
+            (   1,    false,   false,   "177"),
+            (   2,    false,   false,   "200"),
+            (   3,    false,   false,   "377"),
+
+            // index  prefix   padded   expected
+            (   0,    false,   true,    "000"),
+            (   1,    false,   true,    "177"),
+            (   2,    false,   true,    "200"),
+            (   3,    false,   true,    "377"),
+
+            // index  prefix   padded   expected
+            (   0,    true,    false,   "0o0"),
+            (   1,    true,    false,   "0o177"),
+            (   2,    true,    false,   "0o200"),
+            (   3,    true,    false,   "0o377"),
+
+            // index  prefix   padded   expected
+            (   0,    true,    true,    "0o000"),
+            (   1,    true,    true,    "0o177"),
+            (   2,    true,    true,    "0o200"),
+            (   3,    true,    true,    "0o377"),
+        ];
+
+        for (index, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U8.to_string(
+                    context,
+                    SizedDisplay::Octal(OctalOptions {
+                        prefix,
+                        padded,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_octal_u16() -> SimpleResult<()> {
+        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  prefix   padded   expected
+            (   0,    false,   false,   "0"),
+            (   2,    false,   false,   "11064"),
+            (   4,    false,   false,   "177777"),
+
+            // index  prefix   padded   expected
+            (   0,    false,   true,    "000000"),
+            (   2,    false,   true,    "011064"),
+            (   4,    false,   true,    "177777"),
+
+            // index  prefix   padded   expected
+            (   0,    true,    false,   "0o0"),
+            (   2,    true,    false,   "0o11064"),
+            (   4,    true,    false,   "0o177777"),
+
+            // index  prefix   padded   expected
+            (   0,    true,    true,    "0o000000"),
+            (   2,    true,    true,    "0o011064"),
+            (   4,    true,    true,    "0o177777"),
+        ];
+
+        for (index, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U16(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Octal(OctalOptions {
+                        prefix,
+                        padded,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_octal_u32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  prefix  padded   expected
+            (   0,    false,  false,   "11064"),
+            (   2,    false,  false,   "2215177777"),
+            (   4,    false,  false,   "37777777777"),
+
+            // index  prefix  padded   expected
+            (   0,    false,  true,   "00000011064"),
+            (   2,    false,  true,   "02215177777"),
+            (   4,    false,  true,   "37777777777"),
+
+            // index  prefix  padded   expected
+            (   0,    true,  false,   "0o11064"),
+            (   2,    true,  false,   "0o2215177777"),
+            (   4,    true,  false,   "0o37777777777"),
+
+            // index  prefix  padded   expected
+            (   0,    true,  true,     "0o00000011064"),
+            (   2,    true,  true,     "0o02215177777"),
+            (   4,    true,  true,     "0o37777777777"),
+        ];
+
+        for (index, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U32(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Octal(OctalOptions {
+                        prefix,
+                        padded,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_octal_u64() -> SimpleResult<()> {
+        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index  prefix  padded  expected
+            (   0,    false,  false,  "443237777777777"),
+            (   8,    false,  false,  "1777777777777777777777"),
+
+            // index  prefix  padded  expected
+            (   0,    false,  true,   "0000000443237777777777"),
+            (   8,    false,  true,   "1777777777777777777777"),
+
+            // index  prefix  padded  expected
+            (   0,    true,   false,  "0o443237777777777"),
+            (   8,    true,   false,  "0o1777777777777777777777"),
+
+            // index  prefix  padded  expected
+            (   0,    true,   true,   "0o0000000443237777777777"),
+            (   8,    true,   true,   "0o1777777777777777777777"),
+
+        ];
+
+        for (index, prefix, padded, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U64(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Octal(OctalOptions {
+                        prefix,
+                        padded,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_i8() -> SimpleResult<()> {
+        let data = b"\x00\x00\x12\xab\xFF\xFF\xFF\xFF".to_vec();
+
+        let tests = vec![
+            // index    prefix      padded   expected
+            (   0,      true,       true,    "0b00000000"),
+            (   1,      true,       true,    "0b00000000"),
+            (   2,      true,       true,    "0b00010010"),
+            (   3,      true,       true,    "0b10101011"),
+            (   4,      true,       true,    "0b11111111"),
+            (   5,      true,       true,    "0b11111111"),
+
+            (   0,      false,      false,   "0"),
+            (   1,      false,      false,   "0"),
+            (   2,      false,      false,   "10010"),
+            (   3,      false,      false,   "10101011"),
+            (   4,      false,      false,   "11111111"),
+            (   5,      false,      false,   "11111111"),
+        ];
+
+        for (index, padded, prefix, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U8.to_string(
+                    context,
+                    SizedDisplay::Binary(BinaryOptions {
+                        prefix,
+                        padded,
+                        in_place: false,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scientific_u32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\x7f\xff\xff\xff\x80\x00\x00\x00\xff\xff\xff\xff".to_vec();
+
+        let tests = vec![
+            // index  uppercase  expected
+            (   0,    false,     "0e0"),
+            (   4,    false,     "2.147483647e9"),
+            (   8,    false,     "2.147483648e9"),
+            (  12,    false,     "4.294967295e9"),
+            (   0,    true,      "0E0"),
+            (   4,    true,      "2.147483647E9"),
+            (   8,    true,      "2.147483648E9"),
+            (  12,    true,      "4.294967295E9"),
+        ];
+
+        for (index, uppercase, expected) in tests {
+            let context = Context::new_at(&data, index);
+
+            assert_eq!(
+                expected,
+                SizedDefinition::U32(Endian::Big).to_string(
+                    context,
+                    SizedDisplay::Scientific(ScientificOptions {
+                        uppercase,
+                    })
+                )?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scientific_i32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\x7f\xff\xff\xff\x80\x00\x00\x00\xff\xff\xff\xff".to_vec();
+
+        let tests = vec![
+            // index  uppercase  expected
+            (   0,    false,     "0e0"),
+            (   4,    false,     "2.147483647e9"),
+            (   8,    false,     "-2.147483648e9"),
+            (  12,    false,     "-1e0"),
+            (   0,    true,      "0E0"),
+            (   4,    true,      "2.147483647E9"),
+            (   8,    true,      "-2.147483648E9"),
+            (  12,    true,      "-1E0"),
+        ];
+
+        for (index, uppercase, expected) in tests {
             let context = Context::new_at(&data, index);
 
             assert_eq!(
                 expected,
-                SizedDefinition::U16(Endian::Big).to_string(
+                SizedDefinition::I32(Endian::Big).to_string(
                     context,
-                    SizedDisplay::Hex(HexOptions {
-                        uppercase: uppercase,
-                        prefix: prefix,
-                        padded: padded,
+                    SizedDisplay::Scientific(ScientificOptions {
+                        uppercase,
                     })
                 )?
             );
@@ -821,37 +2654,25 @@ mod tests {
     }
 
     #[test]
-    fn test_hex_u32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+    fn test_decimal_f32() -> SimpleResult<()> {
+        let data = b"\x00\x00\x00\x00\xff\xff\xff\xff\x41\xc8\x00\x00\x40\x48\xf5\xc3".to_vec();
 
         let tests = vec![
-            // index  uppercase   prefix   padded    expected
-            (   0,    false,      false,   false,    "1234"),
-            (   0,    true,       false,   false,    "1234"),
-            (   0,    false,      true,    false,    "0x1234"),
-            (   0,    false,      false,   true,     "00001234"),
-            (   0,    true,       true,    true,     "0x00001234"),
-
-            // index  uppercase   prefix   padded    expected
-            (   4,    false,      false,   false,    "ffffffff"),
-            (   4,    true,       false,   false,    "FFFFFFFF"),
-            (   4,    false,      true,    false,    "0xffffffff"),
-            (   4,    false,      false,   true,     "ffffffff"),
-            (   4,    true,       true,    true,     "0xFFFFFFFF"),
+            // index  expected
+            (   0,    "0"),
+            (   4,    "NaN"),
+            (   8,    "25"), // From https://en.wikipedia.org/wiki/Single-precision_floating-point_format#Converting_from_single-precision_binary_to_decimal
+            (  12,    "3.14"),
         ];
 
-        for (index, uppercase, prefix, padded, expected) in tests {
+        for (index, expected) in tests {
             let context = Context::new_at(&data, index);
 
             assert_eq!(
                 expected,
-                SizedDefinition::U32(Endian::Big).to_string(
+                SizedDefinition::F32(Endian::Big).to_string(
                     context,
-                    SizedDisplay::Hex(HexOptions {
-                        uppercase: uppercase,
-                        prefix: prefix,
-                        padded: padded,
-                    })
+                    SizedDisplay::Decimal
                 )?
             );
         }
@@ -860,30 +2681,24 @@ mod tests {
     }
 
     #[test]
-    fn test_hex_u64_big_endian() -> SimpleResult<()> {
-        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+    fn test_decimal_f64_big_endian() -> SimpleResult<()> {
+        // I wrote and disassembled a simple C program to get these strings.. double is hard
+        let data = b"\x40\x09\x1e\xb8\x51\xeb\x85\x1f\x40\x09\x33\x33\x33\x33\x33\x33".to_vec();
 
         let tests = vec![
-            // index  uppercase   prefix   padded    expected
-            (   0,    false,      false,   false,    "1234ffffffff"),
-            (   0,    true,       false,   false,    "1234FFFFFFFF"),
-            (   0,    false,      true,    false,    "0x1234ffffffff"),
-            (   0,    false,      false,   true,     "00001234ffffffff"),
-            (   0,    true,       true,    true,     "0x00001234FFFFFFFF"),
+            // index  expected
+            (   0,    "3.14"),
+            (   8,    "3.15"),
         ];
 
-        for (index, uppercase, prefix, padded, expected) in tests {
+        for (index, expected) in tests {
             let context = Context::new_at(&data, index);
 
             assert_eq!(
                 expected,
-                SizedDefinition::U64(Endian::Big).to_string(
+                SizedDefinition::F64(Endian::Big).to_string(
                     context,
-                    SizedDisplay::Hex(HexOptions {
-                        uppercase: uppercase,
-                        prefix: prefix,
-                        padded: padded,
-                    })
+                    SizedDisplay::Decimal
                 )?
             );
         }
@@ -892,30 +2707,24 @@ mod tests {
     }
 
     #[test]
-    fn test_hex_u64_little_endian() -> SimpleResult<()> {
-        let data = b"\x00\x12\x34\xFF\xFF\xFF\xFF\x00".to_vec();
+    fn test_decimal_f64_little_endian() -> SimpleResult<()> {
+        // I wrote and disassembled a simple C program to get these strings.. double is hard
+        let data = b"\x1F\x85\xEB\x51\xB8\x1E\x09\x40\x33\x33\x33\x33\x33\x33\x09\x40".to_vec();
 
         let tests = vec![
-            // index  uppercase   prefix   padded    expected
-            (   0,    false,      false,   false,    "ffffffff341200"),
-            (   0,    true,       false,   false,    "FFFFFFFF341200"),
-            (   0,    false,      true,    false,    "0xffffffff341200"),
-            (   0,    false,      false,   true,     "00ffffffff341200"),
-            (   0,    true,       true,    true,     "0x00FFFFFFFF341200"),
+            // index  expected
+            (   0,    "3.14"),
+            (   8,    "3.15"),
         ];
 
-        for (index, uppercase, prefix, padded, expected) in tests {
+        for (index, expected) in tests {
             let context = Context::new_at(&data, index);
 
             assert_eq!(
                 expected,
-                SizedDefinition::U64(Endian::Little).to_string(
+                SizedDefinition::F64(Endian::Little).to_string(
                     context,
-                    SizedDisplay::Hex(HexOptions {
-                        uppercase: uppercase,
-                        prefix: prefix,
-                        padded: padded,
-                    })
+                    SizedDisplay::Decimal
                 )?
             );
         }
@@ -924,37 +2733,28 @@ mod tests {
     }
 
     #[test]
-    fn test_hex_u128_big_endian() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff".to_vec();
+    fn test_exponent_f64() -> SimpleResult<()> {
+        // I wrote and disassembled a simple C program to get these strings.. double is hard
+        let data = b"\x40\x09\x1e\xb8\x51\xeb\x85\x1f\x40\x09\x33\x33\x33\x33\x33\x33".to_vec();
 
         let tests = vec![
-            // index  uppercase   prefix   padded    expected
-            (   0,    false,      false,   false,    "1"),
-            (   0,    true,       false,   false,    "1"),
-            (   0,    false,      true,    false,    "0x1"),
-            (   0,    false,      false,   true,     "00000000000000000000000000000001"),
-            (   0,    true,       true,    true,     "0x00000000000000000000000000000001"),
-
-            // index  uppercase   prefix   padded    expected
-            (   16,    false,      false,   false,    "112233445566778899aabbccddeeff"),
-            (   16,    true,       false,   false,    "112233445566778899AABBCCDDEEFF"),
-            (   16,    false,      true,    false,    "0x112233445566778899aabbccddeeff"),
-            (   16,    false,      false,   true,     "00112233445566778899aabbccddeeff"),
-            (   16,    true,       true,    true,     "0x00112233445566778899AABBCCDDEEFF"),
+            // index  uppercase expected
+            (   0,    false,    "3.14e0"),
+            (   8,    false,    "3.15e0"),
+            (   0,    true,     "3.14E0"),
+            (   8,    true,     "3.15E0"),
         ];
 
-        for (index, uppercase, prefix, padded, expected) in tests {
+        for (index, uppercase, expected) in tests {
             let context = Context::new_at(&data, index);
 
             assert_eq!(
                 expected,
-                SizedDefinition::U128(Endian::Big).to_string(
+                SizedDefinition::F64(Endian::Big).to_string(
                     context,
-                    SizedDisplay::Hex(HexOptions {
-                        uppercase: uppercase,
-                        prefix: prefix,
-                        padded: padded,
-                    })
+                    SizedDisplay::Scientific(ScientificOptions {
+                        uppercase,
+                    }),
                 )?
             );
         }
@@ -963,160 +2763,684 @@ mod tests {
     }
 
     #[test]
-    fn test_decimal_u8() -> SimpleResult<()> {
-        let data = b"\x00\x7F\x80\xFF".to_vec();
+    fn test_buffer_too_short() -> SimpleResult<()> {
+        let data = b"".to_vec();
+        assert!(SizedDefinition::I8.to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+
+        let data = b"A".to_vec();
+        assert!(SizedDefinition::I16(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::F16(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::BF16(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+
+        let data = b"AAA".to_vec();
+        assert!(SizedDefinition::I32(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+
+        let data = b"AAAAAAA".to_vec();
+        assert!(SizedDefinition::I64(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+
+        let data = b"AAAAAAAAAAAAAAA".to_vec();
+        assert!(SizedDefinition::I128(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_to() -> SimpleResult<()> {
+        let data = b"\x80\x01\xff\xfe\x40\x49\x0f\xdb\x00\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        let context = Context::new(&data);
+
+        let definitions = vec![
+            SizedDefinition::U8,
+            SizedDefinition::I16(Endian::Big),
+            SizedDefinition::UN(3, Endian::LittleWordSwapped),
+            SizedDefinition::Bits { container: 2, endian: Endian::Little, offset: 3, width: 7, signed: true },
+            SizedDefinition::F32(Endian::Big),
+            SizedDefinition::F64(Endian::Little),
+            SizedDefinition::F80(Endian::Big),
+            SizedDefinition::ULEB128,
+        ];
+
+        let displays = vec![
+            SizedDisplay::Hex(Default::default()),
+            SizedDisplay::Hex(HexOptions { uppercase: true, prefix: false, padded: false, hex_float: false }),
+            SizedDisplay::Hex(HexOptions { uppercase: true, prefix: true, padded: false, hex_float: true }),
+            SizedDisplay::Decimal,
+            SizedDisplay::Octal(Default::default()),
+            SizedDisplay::Binary(BinaryOptions { in_place: true, ..Default::default() }),
+            SizedDisplay::Scientific(ScientificOptions { uppercase: true }),
+            SizedDisplay::FloatParts,
+        ];
+
+        // Writing appends exactly what to_string returns, or nothing at all
+        for &definition in &definitions {
+            for &display in &displays {
+                let mut s = "> ".to_string();
+
+                match definition.to_string(context, display) {
+                    Ok(expected) => {
+                        definition.write_to(context, display, &mut s)?;
+                        assert_eq!(format!("> {}", expected), s);
+                    },
+                    Err(e) => {
+                        assert_eq!(Err(e), definition.write_to(context, display, &mut s));
+                        assert_eq!("> ", s);
+                    },
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_array() -> SimpleResult<()> {
+        let data: Vec<u8> = (0..=255).map(|i: u32| (i * 37 + 11) as u8).collect();
+        let context = Context::new(&data).at(3);
+
+        let definitions = vec![
+            SizedDefinition::U8,
+            SizedDefinition::I8,
+            SizedDefinition::U16(Endian::Big),
+            SizedDefinition::I16(Endian::LittleWordSwapped),
+            SizedDefinition::U24(Endian::Little),
+            SizedDefinition::I24(Endian::Big),
+            SizedDefinition::U32(Endian::BigWordSwapped),
+            SizedDefinition::I32(Endian::Native),
+            SizedDefinition::U64(Endian::Little),
+            SizedDefinition::I64(Endian::LittleWordSwapped),
+            SizedDefinition::U128(Endian::Big),
+            SizedDefinition::I128(Endian::Little),
+            SizedDefinition::UN(5, Endian::Little),
+            SizedDefinition::IN(6, Endian::BigWordSwapped),
+            SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 4, width: 9, signed: true },
+            SizedDefinition::F16(Endian::Little),
+            SizedDefinition::BF16(Endian::Big),
+            SizedDefinition::F32(Endian::LittleWordSwapped),
+            SizedDefinition::F64(Endian::Big),
+            SizedDefinition::F80(Endian::Little),
+            SizedDefinition::F128(Endian::Big),
+        ];
+
+        // Every value matches reading it on its own
+        for definition in definitions {
+            let size = definition.fixed_size().unwrap();
+            let count = ((data.len() as u64 - 3) / size) as usize;
+
+            let array = definition.read_array(context, count)?;
+            assert_eq!(count, array.len());
+
+            for (i, value) in array.iter().enumerate() {
+                let expected = definition.read(context.at(3 + i as u64 * size))?;
+                assert_eq!(expected.format(SizedDisplay::Hex(Default::default()))?, value.format(SizedDisplay::Hex(Default::default()))?);
+            }
+
+            assert!(definition.read_array(context, count + 1).is_err());
+            assert!(definition.read_array(context, 0)?.is_empty());
+        }
+
+        // Variable-length integers follow each other
+        let data = b"\x01\xe5\x8e\x26\x7f\x80".to_vec();
+        match Context::new(&data).read_array(SizedDefinition::ULEB128, 3)? {
+            SizedArray::U64(v) => assert_eq!(vec![1, 624485, 127], v),
+            a => panic!("Wrong array type: {:?}", a),
+        };
+        match Context::new(&data).read_array(SizedDefinition::SLEB128, 3)? {
+            SizedArray::I64(v) => assert_eq!(vec![1, 624485, -1], v),
+            a => panic!("Wrong array type: {:?}", a),
+        };
+        assert!(Context::new(&data).read_array(SizedDefinition::ULEB128, 4).is_err());
+
+        // Odd sizes can't be word-swapped
+        assert!(SizedDefinition::U24(Endian::BigWordSwapped).read_array(Context::new(&data), 2).is_err());
+
+        // Arrays built by hand with a bad width can't be displayed, but don't
+        // panic either
+        for array in [SizedArray::UN(0, vec![1, 2]), SizedArray::IN(0, vec![-1]), SizedArray::IN(17, vec![5])] {
+            for value in array.iter() {
+                assert!(value.format(SizedDisplay::Decimal).is_err());
+                assert!(value.format(SizedDisplay::Hex(Default::default())).is_err());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_string_array() -> SimpleResult<()> {
+        let data = b"\x01\x02\x03\x04\x05\x06\x07\xe5\x8e\x26\x7f".to_vec();
+        let context = Context::new(&data);
+        let hex = SizedDisplay::Hex(HexOptions { padded: false, ..Default::default() });
+
+        let tests = vec![
+            (SizedDefinition::U8, 3, SizedDisplay::Decimal, ArrayOptions::default(),                                                          "[1, 2, 3]"),
+            (SizedDefinition::U8, 0, SizedDisplay::Decimal, ArrayOptions::default(),                                                          "[]"),
+            (SizedDefinition::U8, 3, hex,                   ArrayOptions { separator: " ".to_string(), brackets: false, ..Default::default() }, "0x1 0x2 0x3"),
+            (SizedDefinition::U8, 5, SizedDisplay::Decimal, ArrayOptions { per_line: Some(2), ..Default::default() },                         "[1, 2,\n 3, 4,\n 5]"),
+            (SizedDefinition::U8, 5, SizedDisplay::Decimal, ArrayOptions { per_line: Some(0), ..Default::default() },                         "[1, 2, 3, 4, 5]"),
+            (SizedDefinition::U8, 5, SizedDisplay::Decimal, ArrayOptions { max_elements: Some(2), ..Default::default() },                     "[1, 2, … 3 more]"),
+            (SizedDefinition::U8, 5, SizedDisplay::Decimal, ArrayOptions { max_elements: Some(0), ..Default::default() },                     "[… 5 more]"),
+            (SizedDefinition::U8, 2, SizedDisplay::Decimal, ArrayOptions { max_elements: Some(2), ..Default::default() },                     "[1, 2]"),
+            (SizedDefinition::U8, 3, SizedDisplay::Decimal, ArrayOptions { per_line: Some(1), brackets: false, offsets: true, ..Default::default() }, "00000000: 1,\n00000001: 2,\n00000002: 3"),
+            (SizedDefinition::U16(Endian::Little), 3, hex,  ArrayOptions { per_line: Some(2), max_elements: Some(2), offsets: true, ..Default::default() }, "00000000: [0x201, 0x403,\n00000004:  … 1 more]"),
+        ];
+
+        for (definition, count, display, options, expected) in tests {
+            assert_eq!(expected, definition.to_string_array(context, count, display, &options)?);
+        }
+
+        // Variable-length values are read one after another
+        assert_eq!("00000007: [624485,\n0000000a:  127]", SizedDefinition::ULEB128.to_string_array(context.at(7), 2, SizedDisplay::Decimal, &ArrayOptions {
+            per_line: Some(1),
+            offsets: true,
+            ..Default::default()
+        })?);
+
+        // Only the values that are shown have to be there
+        assert!(SizedDefinition::U32(Endian::Big).to_string_array(context, 3, SizedDisplay::Decimal, &Default::default()).is_err());
+        assert_eq!("[16909060, … 99 more]", SizedDefinition::U32(Endian::Big).to_string_array(context, 100, SizedDisplay::Decimal, &ArrayOptions {
+            max_elements: Some(1),
+            ..Default::default()
+        })?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_character() -> SimpleResult<()> {
+        let ascii = SizedDisplay::Character(CharOptions { encoding: CharEncoding::Ascii });
+        let latin1 = SizedDisplay::Character(CharOptions { encoding: CharEncoding::Latin1 });
+        let unicode = SizedDisplay::Character(CharOptions { encoding: CharEncoding::Unicode });
+
+        let tests = vec![
+            // definition                           display  expected          data
+            (SizedDefinition::U8,                   ascii,   "A",              b"A".to_vec()),
+            (SizedDefinition::U8,                   ascii,   " ",              b" ".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\0",            b"\x00".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\t",            b"\t".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\n",            b"\n".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\r",            b"\r".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\\\",           b"\\".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\x1b",          b"\x1b".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\x7f",          b"\x7f".to_vec()),
+            (SizedDefinition::U8,                   ascii,   "\\xe9",          b"\xe9".to_vec()),
+            (SizedDefinition::U8,                   latin1,  "é",              b"\xe9".to_vec()),
+            (SizedDefinition::U8,                   latin1,  "\\x85",          b"\x85".to_vec()),
+            (SizedDefinition::U8,                   unicode, "é",              b"\xe9".to_vec()),
+            (SizedDefinition::I8,                   latin1,  "ÿ",              b"\xff".to_vec()),
+            (SizedDefinition::U16(Endian::Big),     latin1,  "\\u{20ac}",      b"\x20\xac".to_vec()),
+            (SizedDefinition::U16(Endian::Big),     unicode, "€",              b"\x20\xac".to_vec()),
+            (SizedDefinition::U16(Endian::Big),     unicode, "\\u{d800}",      b"\xd8\x00".to_vec()),
+            (SizedDefinition::U32(Endian::Little),  unicode, "😀",              b"\x00\xf6\x01\x00".to_vec()),
+            (SizedDefinition::U32(Endian::Big),     unicode, "\\u{110000}",    b"\x00\x11\x00\x00".to_vec()),
+            (SizedDefinition::U32(Endian::Big),     unicode, "\\u{ffffffff}",  b"\xff\xff\xff\xff".to_vec()),
+        ];
+
+        for (definition, display, expected, data) in tests {
+            let context = Context::new(&data);
+            assert_eq!(expected, definition.to_string(context, display)?);
+
+            // Everything that's displayed can be parsed back
+            assert_eq!(data, definition.from_string(expected, display)?);
+        }
+
+        assert!(SizedDefinition::F32(Endian::Big).to_string(Context::new(b"\x00\x00\x00\x41"), ascii).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("A", ascii).is_err());
+        assert!(SizedDefinition::U8.from_string("AB", ascii).is_err());
+        assert!(SizedDefinition::U8.from_string("", ascii).is_err());
+        assert!(SizedDefinition::U8.from_string("\\q", ascii).is_err());
+        assert!(SizedDefinition::U8.from_string("é", ascii).is_err());
+        assert!(SizedDefinition::U8.from_string("€", unicode).is_err());
+        assert_eq!(b"\x20\xac".to_vec(), SizedDefinition::U16(Endian::Big).from_string("€", unicode)?);
+        assert!(SizedDefinition::U16(Endian::Big).from_string("€", latin1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_u64() -> SimpleResult<()> {
+        let data = b"\x00\x7F\x80\xFF\x00\x01\x02\x03".to_vec();
+
+        assert_eq!(0u64,   SizedDefinition::U8.to_u64(Context::new_at(&data, 0))?);
+        assert_eq!(127u64, SizedDefinition::U8.to_u64(Context::new_at(&data, 1))?);
+        assert_eq!(128u64, SizedDefinition::U8.to_u64(Context::new_at(&data, 2))?);
+        assert_eq!(255u64, SizedDefinition::U8.to_u64(Context::new_at(&data, 3))?);
+
+        assert_eq!(127u64,               SizedDefinition::U16(Endian::Big).to_u64(Context::new_at(&data, 0))?);
+        assert_eq!(8356095u64,           SizedDefinition::U32(Endian::Big).to_u64(Context::new_at(&data, 0))?);
+        assert_eq!(35889154747335171u64, SizedDefinition::U64(Endian::Big).to_u64(Context::new_at(&data, 0))?);
+
+        assert!(SizedDefinition::U128(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::I8.to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::I16(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::I32(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::I64(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::F16(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::BF16(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::F64(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_i64() -> SimpleResult<()> {
+        let data = b"\x00\x7F\x80\xFF\x00\x01\x02\x03\x80\x00\x00\x00\x00\x00\x00\x00".to_vec();
+
+        assert_eq!(0i64,                    SizedDefinition::I8.to_i64(Context::new_at(&data, 0))?);
+        assert_eq!(127i64,                  SizedDefinition::I8.to_i64(Context::new_at(&data, 1))?);
+        assert_eq!(-128i64,                 SizedDefinition::I8.to_i64(Context::new_at(&data, 2))?);
+        assert_eq!(-1i64,                   SizedDefinition::I8.to_i64(Context::new_at(&data, 3))?);
+
+        assert_eq!(127i64,                  SizedDefinition::I16(Endian::Big).to_i64(Context::new_at(&data, 0))?);
+        assert_eq!(-32768i64,               SizedDefinition::I16(Endian::Big).to_i64(Context::new_at(&data, 8))?);
+
+        assert_eq!(8356095i64,              SizedDefinition::I32(Endian::Big).to_i64(Context::new_at(&data, 0))?);
+        assert_eq!(-2147483648i64,          SizedDefinition::I32(Endian::Big).to_i64(Context::new_at(&data, 8))?);
+
+        assert_eq!(35889154747335171i64,    SizedDefinition::I64(Endian::Big).to_i64(Context::new_at(&data, 0))?);
+        assert_eq!(-9223372036854775808i64, SizedDefinition::I64(Endian::Big).to_i64(Context::new_at(&data, 8))?);
+
+        assert!(SizedDefinition::I128(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+        assert_eq!(0i64,                    SizedDefinition::U8.to_i64(Context::new_at(&data, 0))?);
+        assert_eq!(127i64,                  SizedDefinition::U16(Endian::Big).to_i64(Context::new_at(&data, 0))?);
+        assert_eq!(8356095i64,              SizedDefinition::U32(Endian::Big).to_i64(Context::new_at(&data, 0))?);
+        assert!(SizedDefinition::U64(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::F16(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::BF16(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+        assert!(SizedDefinition::F64(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_string() -> SimpleResult<()> {
+        let tests = vec![
+            // definition                          display                                        string               expected
+            (SizedDefinition::U8,                    SizedDisplay::Hex(Default::default()),         "0x41",              b"\x41".to_vec()),
+            (SizedDefinition::U8,                    SizedDisplay::Hex(Default::default()),         "0xFF",              b"\xff".to_vec()),
+            (SizedDefinition::U8,                    SizedDisplay::Decimal,                         "255",               b"\xff".to_vec()),
+            (SizedDefinition::U8,                    SizedDisplay::Octal(Default::default()),       "0o377",             b"\xff".to_vec()),
+            (SizedDefinition::U8,                    SizedDisplay::Binary(Default::default()),      "0b00000001",        b"\x01".to_vec()),
+            (SizedDefinition::U8,                    SizedDisplay::Scientific(Default::default()),  "1e2",               b"\x64".to_vec()),
+            (SizedDefinition::U16(Endian::Big),      SizedDisplay::Hex(Default::default()),         "0x00ab",            b"\x00\xab".to_vec()),
+            (SizedDefinition::U16(Endian::Little),   SizedDisplay::Hex(Default::default()),         "0xab",              b"\xab\x00".to_vec()),
+            (SizedDefinition::U32(Endian::Big),      SizedDisplay::Scientific(Default::default()),  "1.094861636e9",     b"ABCD".to_vec()),
+            (SizedDefinition::U32(Endian::Big),      SizedDisplay::Scientific(Default::default()),  "1.094861636E9",     b"ABCD".to_vec()),
+            (SizedDefinition::U128(Endian::Big),     SizedDisplay::Decimal,                         "340282366920938463463374607431768211455", b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff".to_vec()),
+
+            (SizedDefinition::I8,                    SizedDisplay::Decimal,                         "-1",                b"\xff".to_vec()),
+            (SizedDefinition::I8,                    SizedDisplay::Decimal,                         "-128",              b"\x80".to_vec()),
+            (SizedDefinition::I8,                    SizedDisplay::Hex(Default::default()),         "0x80",              b"\x80".to_vec()),
+            (SizedDefinition::I16(Endian::Big),      SizedDisplay::Binary(Default::default()),      "0b1111111111111110", b"\xff\xfe".to_vec()),
+            (SizedDefinition::I32(Endian::Big),      SizedDisplay::Scientific(Default::default()),  "-2.147483648e9",    b"\x80\x00\x00\x00".to_vec()),
+            (SizedDefinition::I32(Endian::Little),   SizedDisplay::Scientific(Default::default()),  "-1e0",              b"\xff\xff\xff\xff".to_vec()),
+            (SizedDefinition::I128(Endian::Big),     SizedDisplay::Decimal,                         "-1",                b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff".to_vec()),
+
+            (SizedDefinition::F32(Endian::Big),      SizedDisplay::Decimal,                         "25",                b"\x41\xc8\x00\x00".to_vec()),
+            (SizedDefinition::F64(Endian::Big),      SizedDisplay::Decimal,                         "3.15",              b"\x40\x09\x33\x33\x33\x33\x33\x33".to_vec()),
+            (SizedDefinition::F64(Endian::Little),   SizedDisplay::Scientific(Default::default()),  "3.15e0",            b"\x33\x33\x33\x33\x33\x33\x09\x40".to_vec()),
+        ];
+
+        for (definition, display, s, expected) in tests {
+            assert_eq!(expected, definition.from_string(s, display)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_string_round_trip() -> SimpleResult<()> {
+        let data = b"\x00\x7f\x80\xff\x12\x34\x56\x78\x9a\xbc\xde\xf0\x01\x02\x03\x04\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff".to_vec();
+
+        let definitions = vec![
+            SizedDefinition::U8,
+            SizedDefinition::U16(Endian::Big),
+            SizedDefinition::U32(Endian::Little),
+            SizedDefinition::U64(Endian::Big),
+            SizedDefinition::U128(Endian::Little),
+            SizedDefinition::I8,
+            SizedDefinition::I16(Endian::Little),
+            SizedDefinition::I32(Endian::Big),
+            SizedDefinition::I64(Endian::Little),
+            SizedDefinition::I128(Endian::Big),
+        ];
+
+        let displays = vec![
+            SizedDisplay::Hex(Default::default()),
+            SizedDisplay::Hex(HexOptions { uppercase: true, prefix: false, padded: false, hex_float: false }),
+            SizedDisplay::Decimal,
+            SizedDisplay::Octal(Default::default()),
+            SizedDisplay::Octal(OctalOptions { prefix: false, padded: true }),
+            SizedDisplay::Binary(Default::default()),
+            SizedDisplay::Binary(BinaryOptions { prefix: false, padded: false, in_place: false }),
+            SizedDisplay::Scientific(Default::default()),
+            SizedDisplay::Scientific(ScientificOptions { uppercase: true }),
+        ];
+
+        for &definition in &definitions {
+            for &display in &displays {
+                for index in 0..=(data.len() as u64 - definition.fixed_size().unwrap()) {
+                    let context = Context::new_at(&data, index);
+                    let s = definition.to_string(context, display)?;
+
+                    assert_eq!(context.read_bytes(definition.fixed_size().unwrap() as usize)?, definition.from_string(&s, display)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_string_errors() -> SimpleResult<()> {
+        let hex = SizedDisplay::Hex(Default::default());
+        let scientific = SizedDisplay::Scientific(Default::default());
+
+        // Out of range
+        assert!(SizedDefinition::U8.from_string("256", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::U8.from_string("0x100", hex).is_err());
+        assert!(SizedDefinition::U8.from_string("-1", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::I8.from_string("128", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::I8.from_string("-129", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::U16(Endian::Big).from_string("6.5536e4", scientific).is_err());
+        assert!(SizedDefinition::U128(Endian::Big).from_string("340282366920938463463374607431768211456", SizedDisplay::Decimal).is_err());
+
+        // Prefix doesn't match the options
+        assert!(SizedDefinition::U8.from_string("41", hex).is_err());
+        assert!(SizedDefinition::U8.from_string("0x41", SizedDisplay::Hex(HexOptions { uppercase: false, prefix: false, padded: false, hex_float: false })).is_err());
+        assert!(SizedDefinition::U8.from_string("0b1", SizedDisplay::Binary(BinaryOptions { prefix: false, padded: false, in_place: false })).is_err());
+
+        // Garbage
+        assert!(SizedDefinition::U8.from_string("", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::U8.from_string("+1", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::U8.from_string("0x", hex).is_err());
+        assert!(SizedDefinition::U8.from_string("0xgg", hex).is_err());
+        assert!(SizedDefinition::U8.from_string("0o8", SizedDisplay::Octal(Default::default())).is_err());
+        assert!(SizedDefinition::U8.from_string("0b2", SizedDisplay::Binary(Default::default())).is_err());
+        assert!(SizedDefinition::U8.from_string("1.5e0", scientific).is_err());
+        assert!(SizedDefinition::U8.from_string("100", scientific).is_err());
+        assert!(SizedDefinition::U32(Endian::Big).from_string("1.5e-9223372036854775808", scientific).is_err());
+        assert!(SizedDefinition::I32(Endian::Big).from_string("1e-9223372036854775808", scientific).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("abc", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x100000000", hex).is_err());
+
+        // Widths that can't exist
+        for (s, display) in [("1", SizedDisplay::Decimal), ("0x01", hex), ("1e0", scientific)] {
+            assert!(SizedDefinition::U8.from_string(s, display).is_ok());
+
+            for bytes in [0, 17] {
+                assert!(SizedDefinition::UN(bytes, Endian::Big).from_string(s, display).is_err());
+                assert!(SizedDefinition::IN(bytes, Endian::Big).from_string(s, display).is_err());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read() -> SimpleResult<()> {
+        let data = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x41\xc8\x00\x00\x3e\x00".to_vec();
+        let context = Context::new(&data);
 
         let tests = vec![
-            // index  expected
-            (   0,    "0"),
-            (   1,    "127"),
-            (   2,    "128"),
-            (   3,    "255"),
+            // definition                           expected
+            (SizedDefinition::U8,                   SizedValue::U8(u8::MAX)),
+            (SizedDefinition::U16(Endian::Big),     SizedValue::U16(u16::MAX)),
+            (SizedDefinition::U32(Endian::Big),     SizedValue::U32(u32::MAX)),
+            (SizedDefinition::U64(Endian::Big),     SizedValue::U64(u64::MAX)),
+            (SizedDefinition::U128(Endian::Big),    SizedValue::U128(u128::MAX)),
+            (SizedDefinition::I8,                   SizedValue::I8(-1)),
+            (SizedDefinition::I16(Endian::Big),     SizedValue::I16(-1)),
+            (SizedDefinition::I32(Endian::Big),     SizedValue::I32(-1)),
+            (SizedDefinition::I64(Endian::Big),     SizedValue::I64(-1)),
+            (SizedDefinition::I128(Endian::Big),    SizedValue::I128(-1)),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for (definition, expected) in tests {
+            let v = definition.read(context)?;
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U8.to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+            // Compare the debug output too, to make sure the variant matches
+            assert_eq!(format!("{:?}", expected), format!("{:?}", v));
         }
 
+        assert_eq!(format!("{:?}", SizedValue::F32(25.0)), format!("{:?}", SizedDefinition::F32(Endian::Big).read(context.at(16))?));
+        assert_eq!(format!("{:?}", SizedValue::BF16(bf16::from_f32(25.0))), format!("{:?}", SizedDefinition::BF16(Endian::Big).read(context.at(16))?));
+        assert_eq!(format!("{:?}", SizedValue::F16(f16::from_f32(1.5))), format!("{:?}", SizedDefinition::F16(Endian::Big).read(context.at(20))?));
+        assert!(SizedDefinition::F64(Endian::Big).read(context.at(16)).is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_decimal_i8() -> SimpleResult<()> {
-        let data = b"\x00\x7F\x80\xFF".to_vec();
-
+    fn test_definition_strings() -> SimpleResult<()> {
         let tests = vec![
-            // index  expected
-            (   0,    "0"),
-            (   1,    "127"),
-            (   2,    "-128"),
-            (   3,    "-1"),
+            // definition                           string
+            (SizedDefinition::U8,                   "u8"),
+            (SizedDefinition::U16(Endian::Big),     "u16be"),
+            (SizedDefinition::U32(Endian::Little),  "u32le"),
+            (SizedDefinition::U64(Endian::Big),     "u64be"),
+            (SizedDefinition::U128(Endian::Little), "u128le"),
+            (SizedDefinition::I8,                   "i8"),
+            (SizedDefinition::I16(Endian::Little),  "i16le"),
+            (SizedDefinition::I32(Endian::Big),     "i32be"),
+            (SizedDefinition::I64(Endian::Little),  "i64le"),
+            (SizedDefinition::I128(Endian::Big),    "i128be"),
+            (SizedDefinition::F16(Endian::Big),     "f16be"),
+            (SizedDefinition::BF16(Endian::Little), "bf16le"),
+            (SizedDefinition::F32(Endian::Little),  "f32le"),
+            (SizedDefinition::F64(Endian::Big),     "f64be"),
+            (SizedDefinition::U24(Endian::Little),  "u24le"),
+            (SizedDefinition::I24(Endian::Big),     "i24be"),
+            (SizedDefinition::UN(6, Endian::Big),   "u48be"),
+            (SizedDefinition::IN(5, Endian::Little), "i40le"),
+            (SizedDefinition::U32(Endian::LittleWordSwapped), "u32lews"),
+            (SizedDefinition::F32(Endian::BigWordSwapped),    "f32bews"),
+            (SizedDefinition::I64(Endian::Native),            "i64ne"),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
-
-            assert_eq!(
-                expected,
-                SizedDefinition::I8.to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+        for (definition, s) in tests {
+            assert_eq!(s, format!("{}", definition));
+            assert_eq!(definition, s.parse()?);
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_decimal_u16() -> SimpleResult<()> {
-        let data = b"\x00\xFF\x00\x01\x00\x00\xFF\xFF".to_vec();
-
-        let tests = vec![
-            // index  expected
-            (   0,    "255"),
-            (   2,    "1"),
-            (   4,    "0"),
-            (   6,    "65535"),
+    fn test_definition_round_trip() -> SimpleResult<()> {
+        let endians = [Endian::Big, Endian::Little, Endian::BigWordSwapped, Endian::LittleWordSwapped, Endian::Native];
+
+        let mut definitions = vec![
+            SizedDefinition::U8,
+            SizedDefinition::I8,
+            SizedDefinition::ULEB128,
+            SizedDefinition::SLEB128,
+            SizedDefinition::ZigZag,
+            SizedDefinition::GitVarint,
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for &endian in &endians {
+            definitions.extend_from_slice(&[
+                SizedDefinition::U16(endian),
+                SizedDefinition::U24(endian),
+                SizedDefinition::U32(endian),
+                SizedDefinition::U64(endian),
+                SizedDefinition::U128(endian),
+                SizedDefinition::I16(endian),
+                SizedDefinition::I24(endian),
+                SizedDefinition::I32(endian),
+                SizedDefinition::I64(endian),
+                SizedDefinition::I128(endian),
+                SizedDefinition::F16(endian),
+                SizedDefinition::BF16(endian),
+                SizedDefinition::F32(endian),
+                SizedDefinition::F64(endian),
+                SizedDefinition::F80(endian),
+                SizedDefinition::F128(endian),
+            ]);
+
+            // Arbitrary widths that match a standard type parse as that type
+            // (see test_definition_aliases)
+            for &bytes in &[5, 6, 7, 9, 10, 11, 12, 13, 14, 15] {
+                definitions.push(SizedDefinition::UN(bytes, endian));
+                definitions.push(SizedDefinition::IN(bytes, endian));
+            }
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U16(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+            for container in 1..=16 {
+                for &signed in &[false, true] {
+                    definitions.push(SizedDefinition::Bits { container, endian, offset: 0, width: 1, signed });
+                    definitions.push(SizedDefinition::Bits { container, endian, offset: 1, width: container * 8 - 1, signed });
+                }
+            }
+        }
+
+        for definition in definitions {
+            assert_eq!(definition, format!("{}", definition).parse()?, "{}", definition);
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_decimal_u32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\xff\xff\xff\xff\x7f\xff\xff\xff\x80\x00\x00\x00".to_vec();
+    fn test_definition_aliases() -> SimpleResult<()> {
+        // Arbitrary widths that match a standard type become that type
+        assert_eq!("u128be", format!("{}", SizedDefinition::UN(16, Endian::Big)));
+        assert_eq!(SizedDefinition::U128(Endian::Big), "u128be".parse()?);
+        assert_eq!(SizedDefinition::UN(7, Endian::Big), "uint56_t_be".parse()?);
+        assert_eq!(SizedDefinition::IN(9, Endian::Little), "int72-le".parse()?);
+        assert!("u12le".parse::<SizedDefinition>().is_err());
+        assert!("u136le".parse::<SizedDefinition>().is_err());
+        assert!("u48".parse::<SizedDefinition>().is_err());
 
         let tests = vec![
-            // index  expected
-            (   0,    "0"),
-            (   4,    "4294967295"),
-            (   8,    "2147483647"),
-            (  12,    "2147483648"),
+            // string           definition
+            ("byte",            SizedDefinition::U8),
+            ("uint8_t",         SizedDefinition::U8),
+            ("u8le",            SizedDefinition::U8),
+            ("int8_t",          SizedDefinition::I8),
+            ("U16BE",           SizedDefinition::U16(Endian::Big)),
+            ("wordle",          SizedDefinition::U16(Endian::Little)),
+            ("uint32_t_le",     SizedDefinition::U32(Endian::Little)),
+            ("dword-be",        SizedDefinition::U32(Endian::Big)),
+            ("qword_le",        SizedDefinition::U64(Endian::Little)),
+            ("int64_tbe",       SizedDefinition::I64(Endian::Big)),
+            (" short_le ",      SizedDefinition::I16(Endian::Little)),
+            ("half_le",         SizedDefinition::F16(Endian::Little)),
+            ("binary16be",      SizedDefinition::F16(Endian::Big)),
+            ("bfloat16_le",     SizedDefinition::BF16(Endian::Little)),
+            ("float_be",        SizedDefinition::F32(Endian::Big)),
+            ("doublele",        SizedDefinition::F64(Endian::Little)),
+            ("double_be",       SizedDefinition::F64(Endian::Big)),
+            ("dword_pdp",       SizedDefinition::U32(Endian::LittleWordSwapped)),
+            ("double-lews",     SizedDefinition::F64(Endian::LittleWordSwapped)),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
-
-            assert_eq!(
-                expected,
-                SizedDefinition::U32(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+        for (s, definition) in tests {
+            assert_eq!(definition, s.parse()?);
         }
 
+        // Missing endian
+        assert!("u16".parse::<SizedDefinition>().is_err());
+        assert!("double".parse::<SizedDefinition>().is_err());
+        assert!("bf16".parse::<SizedDefinition>().is_err());
+        assert!("uint32_t".parse::<SizedDefinition>().is_err());
+
+        // Unknown
+        assert!("".parse::<SizedDefinition>().is_err());
+        assert!("le".parse::<SizedDefinition>().is_err());
+        assert!("u7le".parse::<SizedDefinition>().is_err());
+        assert!("u32me".parse::<SizedDefinition>().is_err());
+
+        // C's int is whatever size the platform wants
+        assert!("int_le".parse::<SizedDefinition>().is_err());
+        assert!("uint_le".parse::<SizedDefinition>().is_err());
+        assert!("u32lebe".parse::<SizedDefinition>().is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_decimal_i32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\xff\xff\xff\xff\x7f\xff\xff\xff\x80\x00\x00\x00".to_vec();
+    fn test_hex_octal_binary_float() -> SimpleResult<()> {
+        let data = b"\x41\xc8\x00\x00\x80\x00\x00\x01\x40\x09\x33\x33\x33\x33\x33\x33".to_vec();
+        let context = Context::new(&data);
+
+        let hex = SizedDisplay::Hex(Default::default());
+        let unpadded = SizedDisplay::Hex(HexOptions { uppercase: true, prefix: false, padded: false, hex_float: false });
+
+        assert_eq!("0x41c80000",             SizedDefinition::F32(Endian::Big).to_string(context, hex)?);
+        assert_eq!("0x0000c841",             SizedDefinition::F32(Endian::Little).to_string(context, hex)?);
+        assert_eq!("C841",                   SizedDefinition::F32(Endian::Little).to_string(context, unpadded)?);
+        assert_eq!("0x80000001",             SizedDefinition::F32(Endian::Big).to_string(context.at(4), hex)?);
+        assert_eq!("0x4009333333333333",     SizedDefinition::F64(Endian::Big).to_string(context.at(8), hex)?);
+
+        assert_eq!("0o10162000000",          SizedDefinition::F32(Endian::Big).to_string(context, SizedDisplay::Octal(Default::default()))?);
+        assert_eq!("0b01000001110010000000000000000000", SizedDefinition::F32(Endian::Big).to_string(context, SizedDisplay::Binary(Default::default()))?);
+        assert_eq!("0b0100000000001001001100110011001100110011001100110011001100110011", SizedDefinition::F64(Endian::Big).to_string(context.at(8), SizedDisplay::Binary(Default::default()))?);
+
+        Ok(())
+    }
 
+    #[test]
+    fn test_hex_float() -> SimpleResult<()> {
         let tests = vec![
-            // index  expected
-            (   0,    "0"),
-            (   4,    "-1"),
-            (   8,    "2147483647"),
-            (  12,    "-2147483648"),
+            // value                        uppercase  prefix  padded   expected
+            (3.0f64,                        false,     true,   false,   "0x1.8p+1"),
+            (1.0,                           false,     true,   false,   "0x1p+0"),
+            (-1.0,                          false,     true,   false,   "-0x1p+0"),
+            (0.0,                           false,     true,   false,   "0x0p+0"),
+            (-0.0,                          false,     true,   false,   "-0x0p+0"),
+            (0.1,                           false,     true,   false,   "0x1.999999999999ap-4"),
+            (0.1,                           true,      false,  false,   "1.999999999999Ap-4"),
+            (3.0,                           false,     true,   true,    "0x1.8000000000000p+1"),
+            (f64::MAX,                      false,     true,   false,   "0x1.fffffffffffffp+1023"),
+            (f64::MIN_POSITIVE,             false,     true,   false,   "0x1p-1022"),
+            (5e-324,                        false,     true,   false,   "0x0.0000000000001p-1022"),
+            (f64::INFINITY,                 false,     true,   false,   "inf"),
+            (f64::NEG_INFINITY,             true,      true,   false,   "-INF"),
+            (f64::NAN,                      false,     true,   false,   "nan"),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for (value, uppercase, prefix, padded, expected) in tests {
+            let data = value.to_be_bytes().to_vec();
 
             assert_eq!(
                 expected,
-                SizedDefinition::I32(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
+                SizedDefinition::F64(Endian::Big).to_string(
+                    Context::new(&data),
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase,
+                        prefix,
+                        padded,
+                        hex_float: true,
+                    })
                 )?
             );
         }
 
-        Ok(())
-    }
-
-    #[test]
-    fn test_decimal_i64() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x7f\xff\xff\xff\xff\xff\xff\xff\x80\x00\x00\x00\x00\x00\x00\x00\xff\xff\xff\xff\xff\xff\xff\xff".to_vec();
-
         let tests = vec![
-            // index  expected
-            (  0,    "0"),
-            (  8,    "9223372036854775807"),
-            (  16,   "-9223372036854775808"),
-            (  24,   "-1"),
+            // value                        padded   expected
+            (3.0f32,                        false,   "0x1.8p+1"),
+            (3.0,                           true,    "0x1.800000p+1"),
+            (0.1,                           false,   "0x1.99999ap-4"),
+            (f32::MIN_POSITIVE,             false,   "0x1p-126"),
+            (f32::from_bits(1),             false,   "0x0.000002p-126"),
+            (f32::from_bits(0x00400000),    false,   "0x0.8p-126"),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for (value, padded, expected) in tests {
+            let data = value.to_le_bytes().to_vec();
 
             assert_eq!(
                 expected,
-                SizedDefinition::I64(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
+                SizedDefinition::F32(Endian::Little).to_string(
+                    Context::new(&data),
+                    SizedDisplay::Hex(HexOptions {
+                        uppercase: false,
+                        prefix: true,
+                        padded,
+                        hex_float: true,
+                    })
                 )?
             );
         }
@@ -1125,48 +3449,96 @@ mod tests {
     }
 
     #[test]
-    fn test_decimal_u128() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec();
+    fn test_from_string_float() -> SimpleResult<()> {
+        let data = b"\x41\xc8\x00\x00\x80\x00\x00\x01\x00\x40\x00\x00\x7f\x80\x00\x00\xff\x7f\xff\xff\x40\x09\x33\x33\x33\x33\x33\x33".to_vec();
+
+        let displays = vec![
+            SizedDisplay::Hex(Default::default()),
+            SizedDisplay::Hex(HexOptions { uppercase: true, prefix: false, padded: false, hex_float: false }),
+            SizedDisplay::Hex(HexOptions { uppercase: false, prefix: true, padded: false, hex_float: true }),
+            SizedDisplay::Hex(HexOptions { uppercase: true, prefix: false, padded: true, hex_float: true }),
+            SizedDisplay::Octal(Default::default()),
+            SizedDisplay::Binary(Default::default()),
+        ];
 
-        let tests = vec![
-            // index  expected
-            (  0,    "0"),
-            (  16,   "340282366920938463463374607431768211455"),
+        // The raw bits all round trip exactly (except NaN in hex-float, which
+        // doesn't keep its payload)
+        let definitions = vec![
+            SizedDefinition::F16(Endian::Little),
+            SizedDefinition::BF16(Endian::Big),
+            SizedDefinition::F32(Endian::Big),
+            SizedDefinition::F64(Endian::Little),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for &definition in &definitions {
+            for &display in &displays {
+                for index in 0..=(data.len() as u64 - definition.fixed_size().unwrap()) {
+                    let context = Context::new_at(&data, index);
+                    let s = definition.to_string(context, display)?;
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U128(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+                    if s.to_lowercase().ends_with("nan") {
+                        continue;
+                    }
+
+                    assert_eq!(context.read_bytes(definition.fixed_size().unwrap() as usize)?, definition.from_string(&s, display)?);
+                }
+            }
         }
 
+        let hex_float = SizedDisplay::Hex(HexOptions { uppercase: false, prefix: true, padded: false, hex_float: true });
+        assert_eq!(b"\x40\x40\x00\x00".to_vec(), SizedDefinition::F32(Endian::Big).from_string("0x1.8p+1", hex_float)?);
+        assert_eq!(b"\x7f\xc0\x00\x00".to_vec(), SizedDefinition::F32(Endian::Big).from_string("nan", hex_float)?);
+
+        // Exponents way out of range, and NaN payloads that are dropped
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1p+9223372036854775807", hex_float).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1p-9223372036854775808", hex_float).is_err());
+        assert_eq!("nan", SizedDefinition::F32(Endian::Big).to_string(Context::new(b"\x7f\x80\x00\x01"), hex_float)?);
+        assert_eq!(b"\xff\x80\x00\x00".to_vec(), SizedDefinition::F32(Endian::Big).from_string("-inf", hex_float)?);
+        assert_eq!(b"\x3e\x00".to_vec(),         SizedDefinition::F16(Endian::Big).from_string("0x1.8p+0", hex_float)?);
+        assert_eq!(b"\x3e\x00".to_vec(),         SizedDefinition::F16(Endian::Big).from_string("1.5", SizedDisplay::Decimal)?);
+        assert_eq!(b"\xc0\x3f".to_vec(),         SizedDefinition::BF16(Endian::Little).from_string("1.5e0", SizedDisplay::Scientific(Default::default()))?);
+
+        // Not normalized the way we display them
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x3p+0", hex_float).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1p-149", hex_float).is_err());
+
+        // Out of range
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1p+128", hex_float).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1.0000001p+0", hex_float).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1.000001p+0", hex_float).is_err());
+
+        // Garbage
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1.p+0", hex_float).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1p0", hex_float).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("0x1", hex_float).is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_decimal_i128() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec();
-
+    fn test_float_parts_f32() -> SimpleResult<()> {
         let tests = vec![
-            // index  expected
-            (  0,    "0"),
-            (  16,   "-1"),
+            // bits          expected
+            (0x40400000u32,  "sign=+ exponent=128 (unbiased +1) mantissa=0x400000 normal"),
+            (0xbf800000,     "sign=- exponent=127 (unbiased +0) mantissa=0x000000 normal"),
+            (0x00000000,     "sign=+ exponent=0 (unbiased -126) mantissa=0x000000 zero"),
+            (0x80000000,     "sign=- exponent=0 (unbiased -126) mantissa=0x000000 zero"),
+            (0x00000001,     "sign=+ exponent=0 (unbiased -126) mantissa=0x000001 subnormal"),
+            (0x7f800000,     "sign=+ exponent=255 (unbiased +128) mantissa=0x000000 infinity"),
+            (0xff800000,     "sign=- exponent=255 (unbiased +128) mantissa=0x000000 infinity"),
+            (0x7fc00000,     "sign=+ exponent=255 (unbiased +128) mantissa=0x400000 quiet NaN (payload 0x0)"),
+            (0xffc00123,     "sign=- exponent=255 (unbiased +128) mantissa=0x400123 quiet NaN (payload 0x123)"),
+            (0x7f812345,     "sign=+ exponent=255 (unbiased +128) mantissa=0x012345 signalling NaN (payload 0x12345)"),
         ];
 
-        for (index, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for (bits, expected) in tests {
+            let data = bits.to_be_bytes().to_vec();
 
             assert_eq!(
                 expected,
-                SizedDefinition::I128(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
+                SizedDefinition::F32(Endian::Big).to_string(
+                    Context::new(&data),
+                    SizedDisplay::FloatParts,
                 )?
             );
         }
@@ -1175,465 +3547,569 @@ mod tests {
     }
 
     #[test]
-    fn test_octal_u8() -> SimpleResult<()> {
-        let data = b"\x00\x7F\x80\xFF".to_vec();
-
+    fn test_float_parts_f64() -> SimpleResult<()> {
         let tests = vec![
-            // index  prefix   padded   expected
-            (   0,    false,   false,   "0"),
-            (   1,    false,   false,   "177"),
-            (   2,    false,   false,   "200"),
-            (   3,    false,   false,   "377"),
-
-            // index  prefix   padded   expected
-            (   0,    false,   true,    "000"),
-            (   1,    false,   true,    "177"),
-            (   2,    false,   true,    "200"),
-            (   3,    false,   true,    "377"),
-
-            // index  prefix   padded   expected
-            (   0,    true,    false,   "0o0"),
-            (   1,    true,    false,   "0o177"),
-            (   2,    true,    false,   "0o200"),
-            (   3,    true,    false,   "0o377"),
-
-            // index  prefix   padded   expected
-            (   0,    true,    true,    "0o000"),
-            (   1,    true,    true,    "0o177"),
-            (   2,    true,    true,    "0o200"),
-            (   3,    true,    true,    "0o377"),
+            // bits                  expected
+            (0x4009333333333333u64,  "sign=+ exponent=1024 (unbiased +1) mantissa=0x9333333333333 normal"),
+            (0x0000000000000001,     "sign=+ exponent=0 (unbiased -1022) mantissa=0x0000000000001 subnormal"),
+            (0xfff0000000000000,     "sign=- exponent=2047 (unbiased +1024) mantissa=0x0000000000000 infinity"),
+            (0x7ff8000000000000,     "sign=+ exponent=2047 (unbiased +1024) mantissa=0x8000000000000 quiet NaN (payload 0x0)"),
+            (0x7ff0000000000001,     "sign=+ exponent=2047 (unbiased +1024) mantissa=0x0000000000001 signalling NaN (payload 0x1)"),
         ];
 
-        for (index, prefix, padded, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for (bits, expected) in tests {
+            let data = bits.to_le_bytes().to_vec();
 
             assert_eq!(
                 expected,
-                SizedDefinition::U8.to_string(
-                    context,
-                    SizedDisplay::Octal(OctalOptions {
-                        prefix: prefix,
-                        padded: padded,
-                    })
+                SizedDefinition::F64(Endian::Little).to_string(
+                    Context::new(&data),
+                    SizedDisplay::FloatParts,
                 )?
             );
         }
 
+        // Not for integers, and not parseable
+        let data = b"\x00\x00\x00\x00".to_vec();
+        assert!(SizedDefinition::U32(Endian::Big).to_string(Context::new(&data), SizedDisplay::FloatParts).is_err());
+        assert!(SizedDefinition::F32(Endian::Big).from_string("sign=+ exponent=0 (unbiased -126) mantissa=0x000000 zero", SizedDisplay::FloatParts).is_err());
+        assert!(SizedDefinition::U8.from_string("0", SizedDisplay::FloatParts).is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_octal_u16() -> SimpleResult<()> {
-        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+    fn test_decimal_f16() -> SimpleResult<()> {
+        let data = b"\x00\x00\x3e\x00\xc0\x00\x7b\xff\x2e\x66\x00\x01\x7c\x00\x7e\x00".to_vec();
 
         let tests = vec![
-            // index  prefix   padded   expected
-            (   0,    false,   false,   "0"),
-            (   2,    false,   false,   "11064"),
-            (   4,    false,   false,   "177777"),
-
-            // index  prefix   padded   expected
-            (   0,    false,   true,    "000000"),
-            (   2,    false,   true,    "011064"),
-            (   4,    false,   true,    "177777"),
-
-            // index  prefix   padded   expected
-            (   0,    true,    false,   "0o0"),
-            (   2,    true,    false,   "0o11064"),
-            (   4,    true,    false,   "0o177777"),
-
-            // index  prefix   padded   expected
-            (   0,    true,    true,    "0o000000"),
-            (   2,    true,    true,    "0o011064"),
-            (   4,    true,    true,    "0o177777"),
+            // index  decimal             scientific
+            (   0,    "0",                "0e0"),
+            (   2,    "1.5",              "1.5e0"),
+            (   4,    "-2",               "-2e0"),
+            (   6,    "65504",            "6.5504e4"),
+            (   8,    "0.099975586",      "9.9975586e-2"),
+            (  10,    "0.000000059604645","5.9604645e-8"),
+            (  12,    "inf",              "inf"),
+            (  14,    "NaN",              "NaN"),
         ];
 
-        for (index, prefix, padded, expected) in tests {
+        for (index, decimal, scientific) in tests {
             let context = Context::new_at(&data, index);
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U16(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Octal(OctalOptions {
-                        prefix: prefix,
-                        padded: padded,
-                    })
-                )?
-            );
+            assert_eq!(decimal, SizedDefinition::F16(Endian::Big).to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(scientific, SizedDefinition::F16(Endian::Big).to_string(context, SizedDisplay::Scientific(Default::default()))?);
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_octal_u32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF".to_vec();
+    fn test_decimal_bf16() -> SimpleResult<()> {
+        let data = b"\x49\x40\xcd\x3d\x7f\x7f\x80\xbf".to_vec();
 
         let tests = vec![
-            // index  prefix  padded   expected
-            (   0,    false,  false,   "11064"),
-            (   2,    false,  false,   "2215177777"),
-            (   4,    false,  false,   "37777777777"),
+            // index  decimal                                    scientific
+            (   0,    "3.140625",                                "3.140625e0"),
+            (   2,    "0.100097656",                             "1.00097656e-1"),
+            (   4,    "338953140000000000000000000000000000000", "3.3895314e38"),
+            (   6,    "-1",                                      "-1e0"),
+        ];
 
-            // index  prefix  padded   expected
-            (   0,    false,  true,   "00000011064"),
-            (   2,    false,  true,   "02215177777"),
-            (   4,    false,  true,   "37777777777"),
+        for (index, decimal, scientific) in tests {
+            let context = Context::new_at(&data, index);
 
-            // index  prefix  padded   expected
-            (   0,    true,  false,   "0o11064"),
-            (   2,    true,  false,   "0o2215177777"),
-            (   4,    true,  false,   "0o37777777777"),
+            assert_eq!(decimal, SizedDefinition::BF16(Endian::Little).to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(scientific, SizedDefinition::BF16(Endian::Little).to_string(context, SizedDisplay::Scientific(Default::default()))?);
+        }
 
-            // index  prefix  padded   expected
-            (   0,    true,  true,     "0o00000011064"),
-            (   2,    true,  true,     "0o02215177777"),
-            (   4,    true,  true,     "0o37777777777"),
-        ];
+        Ok(())
+    }
 
-        for (index, prefix, padded, expected) in tests {
-            let context = Context::new_at(&data, index);
+    #[test]
+    fn test_hex_half() -> SimpleResult<()> {
+        let data = b"\x3e\x00\x00\x01\x40\x49\x00\x01".to_vec();
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U32(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Octal(OctalOptions {
-                        prefix: prefix,
-                        padded: padded,
-                    })
-                )?
-            );
+        let hex = SizedDisplay::Hex(Default::default());
+        let hex_float = SizedDisplay::Hex(HexOptions { uppercase: false, prefix: true, padded: false, hex_float: true });
+        let hex_float_padded = SizedDisplay::Hex(HexOptions { uppercase: false, prefix: true, padded: true, hex_float: true });
+
+        let tests = vec![
+            // definition                          index  display             expected
+            (SizedDefinition::F16(Endian::Big),    0,     hex,                "0x3e00"),
+            (SizedDefinition::F16(Endian::Little), 0,     hex,                "0x003e"),
+            (SizedDefinition::F16(Endian::Big),    0,     hex_float,          "0x1.8p+0"),
+            (SizedDefinition::F16(Endian::Big),    0,     hex_float_padded,   "0x1.800p+0"),
+            (SizedDefinition::F16(Endian::Big),    2,     hex_float,          "0x0.004p-14"),
+            (SizedDefinition::BF16(Endian::Big),   4,     hex,                "0x4049"),
+            (SizedDefinition::BF16(Endian::Big),   4,     hex_float,          "0x1.92p+1"),
+            (SizedDefinition::BF16(Endian::Big),   6,     hex_float,          "0x0.02p-126"),
+            (SizedDefinition::BF16(Endian::Big),   4,     SizedDisplay::Binary(Default::default()), "0b0100000001001001"),
+            (SizedDefinition::F16(Endian::Big),    0,     SizedDisplay::Octal(Default::default()),  "0o37000"),
+            (SizedDefinition::F16(Endian::Big),    0,     SizedDisplay::FloatParts, "sign=+ exponent=15 (unbiased +0) mantissa=0x200 normal"),
+            (SizedDefinition::BF16(Endian::Big),   4,     SizedDisplay::FloatParts, "sign=+ exponent=128 (unbiased +1) mantissa=0x49 normal"),
+        ];
+
+        for (definition, index, display, expected) in tests {
+            assert_eq!(expected, definition.to_string(Context::new_at(&data, index), display)?);
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_octal_u64() -> SimpleResult<()> {
-        let data = b"\x00\x00\x12\x34\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec();
+    fn test_decimal_f80() -> SimpleResult<()> {
+        let data = b"\x40\x00\xc9\x0f\xda\xa2\x21\x68\xc2\x35\xcd\xcc\xcc\xcc\xcc\xcc\xcc\xcc\xfb\x3f".to_vec();
 
         let tests = vec![
-            // index  prefix  padded  expected
-            (   0,    false,  false,  "443237777777777"),
-            (   8,    false,  false,  "1777777777777777777777"),
-
-            // index  prefix  padded  expected
-            (   0,    false,  true,   "0000000443237777777777"),
-            (   8,    false,  true,   "1777777777777777777777"),
-
-            // index  prefix  padded  expected
-            (   0,    true,   false,  "0o443237777777777"),
-            (   8,    true,   false,  "0o1777777777777777777777"),
-
-            // index  prefix  padded  expected
-            (   0,    true,   true,   "0o0000000443237777777777"),
-            (   8,    true,   true,   "0o1777777777777777777777"),
-
+            // index   endian          decimal                    scientific
+            (  0,      Endian::Big,    "3.1415926535897932385",   "3.1415926535897932385e0"),
+            (  10,     Endian::Little, "0.1",                     "1e-1"),
         ];
 
-        for (index, prefix, padded, expected) in tests {
+        for (index, endian, decimal, scientific) in tests {
             let context = Context::new_at(&data, index);
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U64(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Octal(OctalOptions {
-                        prefix: prefix,
-                        padded: padded,
-                    })
-                )?
-            );
+            assert_eq!(Some(10), SizedDefinition::F80(endian).fixed_size());
+            assert_eq!(decimal, SizedDefinition::F80(endian).to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(scientific, SizedDefinition::F80(endian).to_string(context, SizedDisplay::Scientific(Default::default()))?);
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_binary_i8() -> SimpleResult<()> {
-        let data = b"\x00\x00\x12\xab\xFF\xFF\xFF\xFF".to_vec();
+    fn test_decimal_f128() -> SimpleResult<()> {
+        let data = b"\x40\x00\x92\x1f\xb5\x44\x42\xd1\x84\x69\x89\x8c\xc5\x17\x01\xb8".to_vec();
 
         let tests = vec![
-            // index    prefix      padded   expected
-            (   0,      true,       true,    "0b00000000"),
-            (   1,      true,       true,    "0b00000000"),
-            (   2,      true,       true,    "0b00010010"),
-            (   3,      true,       true,    "0b10101011"),
-            (   4,      true,       true,    "0b11111111"),
-            (   5,      true,       true,    "0b11111111"),
-
-            (   0,      false,      false,   "0"),
-            (   1,      false,      false,   "0"),
-            (   2,      false,      false,   "10010"),
-            (   3,      false,      false,   "10101011"),
-            (   4,      false,      false,   "11111111"),
-            (   5,      false,      false,   "11111111"),
+            // index   endian          decimal                                  scientific
+            (  0,      Endian::Big,    "3.1415926535897932384626433832795028",  "3.1415926535897932384626433832795028E0"),
         ];
 
-        for (index, padded, prefix, expected) in tests {
+        for (index, endian, decimal, scientific) in tests {
             let context = Context::new_at(&data, index);
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U8.to_string(
-                    context,
-                    SizedDisplay::Binary(BinaryOptions {
-                        prefix: prefix,
-                        padded: padded,
-                    })
-                )?
-            );
+            assert_eq!(Some(16), SizedDefinition::F128(endian).fixed_size());
+            assert_eq!(decimal, SizedDefinition::F128(endian).to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(scientific, SizedDefinition::F128(endian).to_string(context, SizedDisplay::Scientific(ScientificOptions { uppercase: true }))?);
         }
 
+        // Too short
+        assert!(SizedDefinition::F128(Endian::Big).to_string(Context::new_at(&data, 1), SizedDisplay::Decimal).is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_scientific_u32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\x7f\xff\xff\xff\x80\x00\x00\x00\xff\xff\xff\xff".to_vec();
+    fn test_x87_encodings() -> SimpleResult<()> {
+        let hex_float = SizedDisplay::Hex(HexOptions { uppercase: false, prefix: true, padded: false, hex_float: true });
 
         let tests = vec![
-            // index  uppercase  expected
-            (   0,    false,     "0e0"),
-            (   4,    false,     "2.147483647e9"),
-            (   8,    false,     "2.147483648e9"),
-            (  12,    false,     "4.294967295e9"),
-            (   0,    true,      "0E0"),
-            (   4,    true,      "2.147483647E9"),
-            (   8,    true,      "2.147483648E9"),
-            (  12,    true,      "4.294967295E9"),
+            // bits                        decimal (prefix)   hex float       float parts
+            (0x3fff8000000000000000u128,   "1",               "0x1p+0",       "sign=+ exponent=16383 (unbiased +0) integer=1 mantissa=0x0000000000000000 normal"),
+            (0x00000000000000000001,       "0.0000",          "0x0.0000000000000002p-16382", "sign=+ exponent=0 (unbiased -16382) integer=0 mantissa=0x0000000000000001 subnormal"),
+            (0x00008000000000000000,       "0.0000",          "0x1p-16382",   "sign=+ exponent=0 (unbiased -16382) integer=1 mantissa=0x0000000000000000 pseudo-denormal"),
+            (0x3fff4000000000000000,       "0.5",             "0x0.8p+0",     "sign=+ exponent=16383 (unbiased +0) integer=0 mantissa=0x4000000000000000 unnormal"),
+            (0x7fff8000000000000000,       "inf",             "inf",          "sign=+ exponent=32767 (unbiased +16384) integer=1 mantissa=0x0000000000000000 infinity"),
+            (0x7fff0000000000000000,       "NaN",             "nan",          "sign=+ exponent=32767 (unbiased +16384) integer=0 mantissa=0x0000000000000000 pseudo-infinity"),
+            (0xffff0000000000000001,       "NaN",             "-nan",         "sign=- exponent=32767 (unbiased +16384) integer=0 mantissa=0x0000000000000001 pseudo-NaN"),
+            (0x7fffc000000000000001,       "NaN",             "nan",          "sign=+ exponent=32767 (unbiased +16384) integer=1 mantissa=0x4000000000000001 quiet NaN (payload 0x1)"),
         ];
 
-        for (index, uppercase, expected) in tests {
-            let context = Context::new_at(&data, index);
+        for (bits, decimal, hex, parts) in tests {
+            let data = bits.to_be_bytes()[6..].to_vec();
+            let context = Context::new(&data);
+            let d = SizedDefinition::F80(Endian::Big);
 
-            assert_eq!(
-                expected,
-                SizedDefinition::U32(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Scientific(ScientificOptions {
-                        uppercase: uppercase,
-                    })
-                )?
-            );
+            assert!(d.to_string(context, SizedDisplay::Decimal)?.starts_with(decimal));
+            assert_eq!(hex, d.to_string(context, hex_float)?);
+            assert_eq!(parts, d.to_string(context, SizedDisplay::FloatParts)?);
         }
 
+        // The pseudo-denormal is the same value as the smallest normal
+        let data = b"\x00\x00\x80\x00\x00\x00\x00\x00\x00\x00\x00\x01\x80\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        assert_eq!(
+            SizedDefinition::F80(Endian::Big).read(Context::new_at(&data, 0))?,
+            SizedDefinition::F80(Endian::Big).read(Context::new_at(&data, 10))?,
+        );
+
         Ok(())
     }
 
     #[test]
-    fn test_scientific_i32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\x7f\xff\xff\xff\x80\x00\x00\x00\xff\xff\xff\xff".to_vec();
+    fn test_hex_octal_binary_big_floats() -> SimpleResult<()> {
+        let data = b"\x3f\xff\x80\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        let context = Context::new(&data);
 
-        let tests = vec![
-            // index  uppercase  expected
-            (   0,    false,     "0e0"),
-            (   4,    false,     "2.147483647e9"),
-            (   8,    false,     "-2.147483648e9"),
-            (  12,    false,     "-1e0"),
-            (   0,    true,      "0E0"),
-            (   4,    true,      "2.147483647E9"),
-            (   8,    true,      "-2.147483648E9"),
-            (  12,    true,      "-1E0"),
-        ];
+        assert_eq!("0x3fff8000000000000000", SizedDefinition::F80(Endian::Big).to_string(context, SizedDisplay::Hex(Default::default()))?);
+        assert_eq!("0o077777000000000000000000000", SizedDefinition::F80(Endian::Big).to_string(context, SizedDisplay::Octal(OctalOptions { prefix: true, padded: true }))?);
+        assert_eq!(82, SizedDefinition::F80(Endian::Big).to_string(context, SizedDisplay::Binary(Default::default()))?.len());
 
-        for (index, uppercase, expected) in tests {
-            let context = Context::new_at(&data, index);
+        let data = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\xff\x3f".to_vec();
+        let context = Context::new(&data);
 
-            assert_eq!(
-                expected,
-                SizedDefinition::I32(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Scientific(ScientificOptions {
-                        uppercase: uppercase,
-                    })
-                )?
-            );
-        }
+        assert_eq!("0x3fff0000000000000000000000000000", SizedDefinition::F128(Endian::Little).to_string(context, SizedDisplay::Hex(Default::default()))?);
+        assert_eq!("0x1p+0", SizedDefinition::F128(Endian::Little).to_string(context, SizedDisplay::Hex(HexOptions { hex_float: true, padded: false, ..Default::default() }))?);
 
         Ok(())
     }
 
     #[test]
-    fn test_decimal_f32() -> SimpleResult<()> {
-        let data = b"\x00\x00\x00\x00\xff\xff\xff\xff\x41\xc8\x00\x00\x40\x48\xf5\xc3".to_vec();
+    fn test_from_string_big_floats() -> SimpleResult<()> {
+        let displays = vec![
+            SizedDisplay::Decimal,
+            SizedDisplay::Scientific(Default::default()),
+            SizedDisplay::Hex(Default::default()),
+            SizedDisplay::Hex(HexOptions { hex_float: true, ..Default::default() }),
+            SizedDisplay::Octal(Default::default()),
+            SizedDisplay::Binary(Default::default()),
+        ];
+
+        let data = b"\x40\x00\xc9\x0f\xda\xa2\x21\x68\xc2\x35\x3f\xff\x40\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01".to_vec();
 
         let tests = vec![
-            // index  expected
-            (   0,    "0"),
-            (   4,    "NaN"),
-            (   8,    "25"), // From https://en.wikipedia.org/wiki/Single-precision_floating-point_format#Converting_from_single-precision_binary_to_decimal
-            (  12,    "3.14"),
+            // index   definition
+            (  0,      SizedDefinition::F80(Endian::Big)),
+            (  0,      SizedDefinition::F80(Endian::Little)),
+            (  10,     SizedDefinition::F80(Endian::Big)),    // Unnormal
+            (  18,     SizedDefinition::F80(Endian::Big)),    // Subnormal
+            (  0,      SizedDefinition::F128(Endian::Big)),
+            (  12,     SizedDefinition::F128(Endian::Little)),
         ];
 
-        for (index, expected) in tests {
+        for (index, definition) in tests {
             let context = Context::new_at(&data, index);
+            let expected = context.read_bytes(definition.fixed_size().unwrap() as usize)?;
 
-            assert_eq!(
-                expected,
-                SizedDefinition::F32(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+            for display in &displays {
+                // Unnormals can't survive a trip through decimal
+                let unnormal = definition.to_string(context, SizedDisplay::FloatParts)?.ends_with(" unnormal");
+                if unnormal && matches!(display, SizedDisplay::Decimal | SizedDisplay::Scientific(_)) {
+                    continue;
+                }
+
+                let s = definition.to_string(context, *display)?;
+                assert_eq!(expected, definition.from_string(&s, *display)?, "{} as {:?}", definition, display);
+            }
         }
 
+        // Decimal parses to the nearest value
+        assert_eq!(b"\x3f\xfb\xcc\xcc\xcc\xcc\xcc\xcc\xcc\xcd".to_vec(), SizedDefinition::F80(Endian::Big).from_string("0.1", SizedDisplay::Decimal)?);
+
         Ok(())
     }
 
     #[test]
-    fn test_decimal_f64_big_endian() -> SimpleResult<()> {
-        // I wrote and disassembled a simple C program to get these strings.. double is hard
-        let data = b"\x40\x09\x1e\xb8\x51\xeb\x85\x1f\x40\x09\x33\x33\x33\x33\x33\x33".to_vec();
+    fn test_big_float_names() -> SimpleResult<()> {
+        assert_eq!("f80le", format!("{}", SizedDefinition::F80(Endian::Little)));
+        assert_eq!("f128be", format!("{}", SizedDefinition::F128(Endian::Big)));
+        assert_eq!(SizedDefinition::F80(Endian::Little), "extended_le".parse()?);
+        assert_eq!(SizedDefinition::F128(Endian::Big), "quadbe".parse()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_odd_widths() -> SimpleResult<()> {
+        let data = b"\x00\x12\x34\xff\xff\xfe\x80\x00\x00".to_vec();
+        let hex = SizedDisplay::Hex(Default::default());
+        let octal = SizedDisplay::Octal(OctalOptions { prefix: true, padded: true });
+        let binary = SizedDisplay::Binary(Default::default());
 
         let tests = vec![
-            // index  expected
-            (   0,    "3.14"),
-            (   8,    "3.15"),
+            // index  definition                          hex                 decimal          octal                 binary
+            (  0,     SizedDefinition::U24(Endian::Big),  "0x001234",         "4660",          "0o00011064",         "0b000000000001001000110100"),
+            (  3,     SizedDefinition::I24(Endian::Big),  "0xfffffe",         "-2",            "0o77777776",         "0b111111111111111111111110"),
+            (  6,     SizedDefinition::I24(Endian::Big),  "0x800000",         "-8388608",      "0o40000000",         "0b100000000000000000000000"),
+            (  0,     SizedDefinition::UN(5, Endian::Big),"0x001234ffff",     "305463295",     "0o00002215177777",   "0b0000000000010010001101001111111111111111"),
+            (  1,     SizedDefinition::IN(5, Endian::Big),"0x1234fffffe",     "78198603774",   "0o01106477777776",   "0b0001001000110100111111111111111111111110"),
+            (  3,     SizedDefinition::IN(6, Endian::Big),"0xfffffe800000",   "-25165824",     "0o7777777640000000", "0b111111111111111111111110100000000000000000000000"),
+            (  3,     SizedDefinition::IN(2, Endian::Little), "0xffff",       "-1",            "0o177777",           "0b1111111111111111"),
         ];
 
-        for (index, expected) in tests {
+        for (index, definition, expected_hex, expected_decimal, expected_octal, expected_binary) in tests {
             let context = Context::new_at(&data, index);
 
-            assert_eq!(
-                expected,
-                SizedDefinition::F64(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+            assert_eq!(expected_hex,     definition.to_string(context, hex)?);
+            assert_eq!(expected_decimal, definition.to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(expected_octal,   definition.to_string(context, octal)?);
+            assert_eq!(expected_binary,  definition.to_string(context, binary)?);
+
+            // And back again
+            for display in &[hex, SizedDisplay::Decimal, octal, binary, SizedDisplay::Scientific(Default::default())] {
+                let s = definition.to_string(context, *display)?;
+                assert_eq!(context.read_bytes(definition.fixed_size().unwrap() as usize)?, definition.from_string(&s, *display)?);
+            }
         }
 
+        // Values have to fit
+        assert!(SizedDefinition::U24(Endian::Big).from_string("16777216", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::IN(3, Endian::Big).from_string("-8388609", SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::UN(5, Endian::Big).from_string("0x10000000000", hex).is_err());
+
+        // Widths have to make sense
+        assert!(SizedDefinition::UN(0, Endian::Big).read(Context::new(&data)).is_err());
+        assert!(SizedDefinition::IN(17, Endian::Big).read(Context::new(&data)).is_err());
+        assert!(SizedDefinition::UN(0, Endian::Big).from_string("0", SizedDisplay::Decimal).is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_decimal_f64_little_endian() -> SimpleResult<()> {
-        // I wrote and disassembled a simple C program to get these strings.. double is hard
-        let data = b"\x1F\x85\xEB\x51\xB8\x1E\x09\x40\x33\x33\x33\x33\x33\x33\x09\x40".to_vec();
+    fn test_odd_widths_to_64() -> SimpleResult<()> {
+        let data = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01".to_vec();
+        let context = Context::new(&data);
+
+        assert_eq!(0xffffff,           SizedDefinition::U24(Endian::Big).to_u64(context)?);
+        assert_eq!(-1,                 SizedDefinition::I24(Endian::Big).to_i64(context)?);
+        assert_eq!(0xffffffffffff,     SizedDefinition::UN(6, Endian::Big).to_u64(context)?);
+        assert_eq!(u64::MAX,           SizedDefinition::UN(8, Endian::Big).to_u64(context)?);
+        assert_eq!(-1,                 SizedDefinition::IN(7, Endian::Little).to_i64(context)?);
+
+        assert!(SizedDefinition::UN(9, Endian::Big).to_u64(context).is_err());
+        assert!(SizedDefinition::IN(9, Endian::Big).to_i64(context).is_err());
+        assert!(SizedDefinition::IN(3, Endian::Big).to_u64(context).is_err());
+        assert_eq!(0xffffff,           SizedDefinition::U24(Endian::Big).to_i64(context)?);
+        assert!(SizedDefinition::UN(8, Endian::Big).to_i64(context).is_err());
+
+        assert!(SizedDefinition::U24(Endian::Big).can_be_u64());
+        assert!(SizedDefinition::UN(8, Endian::Big).can_be_u64());
+        assert!(!SizedDefinition::UN(9, Endian::Big).can_be_u64());
+        assert!(SizedDefinition::IN(1, Endian::Big).can_be_i64());
+        assert!(!SizedDefinition::IN(0, Endian::Big).can_be_i64());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bits() -> SimpleResult<()> {
+        let data = b"\x00\xa8\xf0\x0f".to_vec();
+        let hex = SizedDisplay::Hex(Default::default());
+        let binary = SizedDisplay::Binary(Default::default());
+        let in_place = SizedDisplay::Binary(BinaryOptions { in_place: true, ..Default::default() });
 
         let tests = vec![
-            // index  expected
-            (   0,    "3.14"),
-            (   8,    "3.15"),
+            // index  container endian          offset width signed    decimal  hex       binary           in place
+            (  0,     2,        Endian::Big,    3,     5,    false,    "21",    "0x15",   "0b10101",       "0b........10101..."),
+            (  0,     2,        Endian::Big,    3,     5,    true,     "-11",   "0x15",   "0b10101",       "0b........10101..."),
+            (  0,     2,        Endian::Little, 8,     8,    false,    "168",   "0xa8",   "0b10101000",    "0b10101000........"),
+            (  1,     1,        Endian::Big,    0,     1,    true,     "0",     "0x0",    "0b0",           "0b.......0"),
+            (  1,     1,        Endian::Big,    7,     1,    true,     "-1",    "0x1",    "0b1",           "0b1......."),
+            (  0,     4,        Endian::Little, 20,    8,    false,    "255",   "0xff",   "0b11111111",    "0b....11111111...................."),
+            (  0,     4,        Endian::Little, 0,     32,   true,     "267429888", "0x0ff0a800", "0b00001111111100001010100000000000", "0b00001111111100001010100000000000"),
         ];
 
-        for (index, expected) in tests {
+        for (index, container, endian, offset, width, signed, decimal, expected_hex, expected_binary, expected_in_place) in tests {
             let context = Context::new_at(&data, index);
-
-            assert_eq!(
-                expected,
-                SizedDefinition::F64(Endian::Little).to_string(
-                    context,
-                    SizedDisplay::Decimal
-                )?
-            );
+            let d = SizedDefinition::Bits { container, endian, offset, width, signed };
+
+            assert_eq!(Some(container as u64), d.fixed_size());
+            assert_eq!(decimal,            d.to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(expected_hex,       d.to_string(context, hex)?);
+            assert_eq!(expected_binary,    d.to_string(context, binary)?);
+            assert_eq!(expected_in_place,  d.to_string(context, in_place)?);
+
+            // Parsing puts the field back in place, with the rest zeroed
+            let expected = SizedDefinition::UN(container, endian).read(context)?;
+            let expected = u128::try_from(expected)? & (bit_mask(width as u32) << offset);
+            let expected = SizedDefinition::UN(container, endian).from_string(&expected.to_string(), SizedDisplay::Decimal)?;
+
+            for display in &[SizedDisplay::Decimal, hex, binary, in_place, SizedDisplay::Octal(Default::default()), SizedDisplay::Scientific(Default::default())] {
+                let s = d.to_string(context, *display)?;
+                assert_eq!(expected, d.from_string(&s, *display)?, "{} as {:?}", s, display);
+            }
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_exponent_f64() -> SimpleResult<()> {
-        // I wrote and disassembled a simple C program to get these strings.. double is hard
-        let data = b"\x40\x09\x1e\xb8\x51\xeb\x85\x1f\x40\x09\x33\x33\x33\x33\x33\x33".to_vec();
+    fn test_bits_errors() -> SimpleResult<()> {
+        let data = b"\x00\x00".to_vec();
+        let context = Context::new(&data);
+        let in_place = SizedDisplay::Binary(BinaryOptions { in_place: true, ..Default::default() });
+
+        // Doesn't fit in the container
+        assert!(SizedDefinition::Bits { container: 1, endian: Endian::Big, offset: 4, width: 5, signed: false }.read(context).is_err());
+        assert!(SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 0, width: 0, signed: false }.read(context).is_err());
+        assert!(SizedDefinition::Bits { container: 17, endian: Endian::Big, offset: 0, width: 1, signed: false }.read(context).is_err());
+
+        // Container is past the end
+        assert!(SizedDefinition::Bits { container: 4, endian: Endian::Big, offset: 0, width: 1, signed: false }.read(context).is_err());
+
+        // Values that don't fit the field
+        let d = SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 3, width: 5, signed: true };
+        assert!(d.from_string("16", SizedDisplay::Decimal).is_err());
+        assert!(d.from_string("-17", SizedDisplay::Decimal).is_err());
+        assert!(d.from_string("0b1.......10101...", in_place).is_err());
+        assert!(d.from_string("0b........10101..", in_place).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bits_to_64() -> SimpleResult<()> {
+        let data = b"\xf0\x00".to_vec();
+        let context = Context::new(&data);
+
+        assert_eq!(15, SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 12, width: 4, signed: false }.to_u64(context)?);
+        assert_eq!(-1, SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 12, width: 4, signed: true }.to_i64(context)?);
+        assert!(SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 12, width: 4, signed: true }.to_u64(context).is_err());
+        assert_eq!(15, SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 12, width: 4, signed: false }.to_i64(context)?);
+
+        Ok(())
+    }
 
+    #[test]
+    fn test_bits_strings() -> SimpleResult<()> {
         let tests = vec![
-            // index  uppercase expected
-            (   0,    false,    "3.14e0"),
-            (   8,    false,    "3.15e0"),
-            (   0,    true,     "3.14E0"),
-            (   8,    true,     "3.15E0"),
+            // definition                                                                                      string
+            (SizedDefinition::Bits { container: 2, endian: Endian::Big, offset: 3, width: 5, signed: false },     "u16be[3..8]"),
+            (SizedDefinition::Bits { container: 4, endian: Endian::Little, offset: 0, width: 1, signed: true },   "i32le[0..1]"),
+            (SizedDefinition::Bits { container: 1, endian: Endian::Big, offset: 4, width: 4, signed: false },     "u8be[4..8]"),
+            (SizedDefinition::Bits { container: 6, endian: Endian::Little, offset: 40, width: 8, signed: false }, "u48le[40..48]"),
         ];
 
-        for (index, uppercase, expected) in tests {
-            let context = Context::new_at(&data, index);
-
-            assert_eq!(
-                expected,
-                SizedDefinition::F64(Endian::Big).to_string(
-                    context,
-                    SizedDisplay::Scientific(ScientificOptions {
-                        uppercase: uppercase,
-                    }),
-                )?
-            );
+        for (definition, s) in tests {
+            assert_eq!(s, format!("{}", definition));
+            assert_eq!(definition, s.parse()?);
         }
 
+        assert_eq!(SizedDefinition::Bits { container: 1, endian: Endian::Big, offset: 0, width: 2, signed: true }, "int8_t[0..2]".parse()?);
+        assert_eq!(SizedDefinition::Bits { container: 1, endian: Endian::Little, offset: 1, width: 3, signed: false }, "u8le[1..4]".parse()?);
+        assert!("u16be[3..17]".parse::<SizedDefinition>().is_err());
+        assert!("u16be[3..3]".parse::<SizedDefinition>().is_err());
+        assert!("u16be[3]".parse::<SizedDefinition>().is_err());
+        assert!("f32be[0..4]".parse::<SizedDefinition>().is_err());
+        assert!("u16[0..4]".parse::<SizedDefinition>().is_err());
+
         Ok(())
     }
 
     #[test]
-    fn test_buffer_too_short() -> SimpleResult<()> {
-        let data = b"".to_vec();
-        assert!(SizedDefinition::I8.to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+    fn test_varints() -> SimpleResult<()> {
+        let data = b"\xe5\x8e\x26\x7f\x80\x01".to_vec();
+        let context = Context::new(&data);
 
-        let data = b"A".to_vec();
-        assert!(SizedDefinition::I16(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+        let tests = vec![
+            // definition                  index  size  decimal     hex
+            (SizedDefinition::ULEB128,     0,     3,    "624485",   "0x0000000000098765"),
+            (SizedDefinition::SLEB128,     0,     3,    "624485",   "0x0000000000098765"),
+            (SizedDefinition::ZigZag,      0,     3,    "-312243",  "0xfffffffffffb3c4d"),
+            (SizedDefinition::GitVarint,   0,     3,    "1673126",  "0x00000000001987a6"),
+            (SizedDefinition::ULEB128,     3,     1,    "127",      "0x000000000000007f"),
+            (SizedDefinition::SLEB128,     3,     1,    "-1",       "0xffffffffffffffff"),
+            (SizedDefinition::ZigZag,      3,     1,    "-64",      "0xffffffffffffffc0"),
+            (SizedDefinition::ULEB128,     4,     2,    "128",      "0x0000000000000080"),
+            (SizedDefinition::GitVarint,   4,     2,    "129",      "0x0000000000000081"),
+        ];
 
-        let data = b"AAA".to_vec();
-        assert!(SizedDefinition::I32(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+        for (definition, index, size, decimal, hex) in tests {
+            let context = context.at(index);
+            let hex_options = HexOptions { uppercase: false, prefix: true, padded: true, hex_float: false };
 
-        let data = b"AAAAAAA".to_vec();
-        assert!(SizedDefinition::I64(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+            assert_eq!(None, definition.fixed_size());
+            assert_eq!(size, definition.size(context)?);
+            assert_eq!(decimal, definition.to_string(context, SizedDisplay::Decimal)?);
+            assert_eq!(hex, definition.to_string(context, SizedDisplay::Hex(hex_options))?);
 
-        let data = b"AAAAAAAAAAAAAAA".to_vec();
-        assert!(SizedDefinition::I128(Endian::Big).to_string(Context::new(&data), SizedDisplay::Decimal).is_err());
+            // Parsing produces the shortest encoding, which these all are
+            let expected = context.read_bytes(size as usize)?;
+            assert_eq!(expected, definition.from_string(decimal, SizedDisplay::Decimal)?);
+            assert_eq!(expected, definition.from_string(hex, SizedDisplay::Hex(hex_options))?);
+        }
+
+        // Off the end of the buffer, or truncated
+        assert!(SizedDefinition::ULEB128.size(context.at(6)).is_err());
+        assert!(SizedDefinition::ULEB128.read(Context::new(b"\x80")).is_err());
+
+        assert_eq!(624485, SizedDefinition::ULEB128.to_u64(context)?);
+        assert_eq!(-312243, SizedDefinition::ZigZag.to_i64(context)?);
+        assert!(SizedDefinition::SLEB128.to_u64(context).is_err());
+
+        let tests = vec![
+            // definition                  string
+            (SizedDefinition::ULEB128,     "uleb128"),
+            (SizedDefinition::SLEB128,     "sleb128"),
+            (SizedDefinition::ZigZag,      "zigzag"),
+            (SizedDefinition::GitVarint,   "git_varint"),
+        ];
+
+        for (definition, s) in tests {
+            assert_eq!(s, format!("{}", definition));
+            assert_eq!(definition, s.parse()?);
+        }
+
+        assert_eq!(SizedDefinition::ULEB128, "varint".parse()?);
+        assert_eq!(SizedDefinition::ZigZag, "sint64".parse()?);
 
         Ok(())
     }
 
     #[test]
-    fn test_to_u64() -> SimpleResult<()> {
-        let data = b"\x00\x7F\x80\xFF\x00\x01\x02\x03".to_vec();
+    fn test_word_swapped() -> SimpleResult<()> {
+        let data = b"\x00\x00\x3f\xc0\x34\x12\x78\x56".to_vec();
+        let context = Context::new(&data);
 
-        assert_eq!(0u64,   SizedDefinition::U8.to_u64(Context::new_at(&data, 0))?);
-        assert_eq!(127u64, SizedDefinition::U8.to_u64(Context::new_at(&data, 1))?);
-        assert_eq!(128u64, SizedDefinition::U8.to_u64(Context::new_at(&data, 2))?);
-        assert_eq!(255u64, SizedDefinition::U8.to_u64(Context::new_at(&data, 3))?);
+        let tests = vec![
+            // index  definition                                     display                                   expected
+            (  0,     SizedDefinition::F32(Endian::BigWordSwapped),    SizedDisplay::Decimal,                    "1.5"),
+            (  4,     SizedDefinition::U32(Endian::LittleWordSwapped), SizedDisplay::Hex(Default::default()),    "0x12345678"),
+            (  4,     SizedDefinition::I32(Endian::BigWordSwapped),    SizedDisplay::Decimal,                    "2018915346"),
+            (  0,     SizedDefinition::U64(Endian::BigWordSwapped),    SizedDisplay::Hex(Default::default()),    "0x3412785600003fc0"),
+        ];
 
-        assert_eq!(127u64,               SizedDefinition::U16(Endian::Big).to_u64(Context::new_at(&data, 0))?);
-        assert_eq!(8356095u64,           SizedDefinition::U32(Endian::Big).to_u64(Context::new_at(&data, 0))?);
-        assert_eq!(35889154747335171u64, SizedDefinition::U64(Endian::Big).to_u64(Context::new_at(&data, 0))?);
+        for (index, definition, display, expected) in tests {
+            let context = context.at(index);
 
-        assert!(SizedDefinition::U128(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::I8.to_u64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::I16(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::I32(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::I64(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::F32(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::F64(Endian::Big).to_u64(Context::new_at(&data, 0)).is_err());
+            assert_eq!(expected, definition.to_string(context, display)?);
+            assert_eq!(context.read_bytes(definition.fixed_size().unwrap() as usize)?, definition.from_string(expected, display)?);
+        }
+
+        // Odd sizes can't be swapped
+        assert!(SizedDefinition::U24(Endian::BigWordSwapped).to_string(context, SizedDisplay::Decimal).is_err());
+        assert!(SizedDefinition::U24(Endian::BigWordSwapped).from_string("1", SizedDisplay::Decimal).is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_to_i64() -> SimpleResult<()> {
-        let data = b"\x00\x7F\x80\xFF\x00\x01\x02\x03\x80\x00\x00\x00\x00\x00\x00\x00".to_vec();
-
-        assert_eq!(0i64,                    SizedDefinition::I8.to_i64(Context::new_at(&data, 0))?);
-        assert_eq!(127i64,                  SizedDefinition::I8.to_i64(Context::new_at(&data, 1))?);
-        assert_eq!(-128i64,                 SizedDefinition::I8.to_i64(Context::new_at(&data, 2))?);
-        assert_eq!(-1i64,                   SizedDefinition::I8.to_i64(Context::new_at(&data, 3))?);
+    fn test_guess_endian() -> SimpleResult<()> {
+        let tests: Vec<(&[u8], SizedDefinition, Option<Endian>)> = vec![
+            // data                                                              definition                              expected
+            (b"\x01\x00\x00\x00\x20\x00\x00\x00",                                SizedDefinition::U32(Endian::Big),      Some(Endian::Little)),
+            (b"\x00\x00\x00\x01\x00\x00\x00\x20",                                SizedDefinition::U32(Endian::Little),   Some(Endian::Big)),
+            (b"\xfe\xff\x10\x00",                                                SizedDefinition::I16(Endian::Big),      Some(Endian::Little)),
+
+            // Pointers - the top bytes are zero, and the bottom bits are aligned
+            (b"\x70\x56\x34\x12\xf6\x7f\x00\x00\x08\x5f\x34\x12\xf6\x7f\x00\x00", SizedDefinition::U64(Endian::Big),      Some(Endian::Little)),
+            (b"\x00\x00\x55\x55\x12\x34\x56\x78",                                SizedDefinition::U64(Endian::Little),   Some(Endian::Big)),
+
+            // 1.5 and -100.0
+            (b"\x00\x00\xc0\x3f\x00\x00\xc8\xc2",                                SizedDefinition::F32(Endian::Big),      Some(Endian::Little)),
+            (b"\x3f\xf8\x00\x00\x00\x00\x00\x00",                                SizedDefinition::F64(Endian::Little),   Some(Endian::Big)),
+
+            // Symmetrical data can't be told apart
+            (b"\x00\x00\x00\x00\xff\xff\xff\xff",                                SizedDefinition::U32(Endian::Big),      None),
+            (b"\x12\x34\x34\x12",                                                SizedDefinition::U32(Endian::Big),      None),
+        ];
 
-        assert_eq!(127i64,                  SizedDefinition::I16(Endian::Big).to_i64(Context::new_at(&data, 0))?);
-        assert_eq!(-32768i64,               SizedDefinition::I16(Endian::Big).to_i64(Context::new_at(&data, 8))?);
+        for (data, definition, expected) in tests {
+            let data = data.to_vec();
+            let count = data.len() as u64 / definition.fixed_size().unwrap();
 
-        assert_eq!(8356095i64,              SizedDefinition::I32(Endian::Big).to_i64(Context::new_at(&data, 0))?);
-        assert_eq!(-2147483648i64,          SizedDefinition::I32(Endian::Big).to_i64(Context::new_at(&data, 8))?);
+            assert_eq!(expected, definition.guess_endian(Context::new(&data), count)?);
+        }
 
-        assert_eq!(35889154747335171i64,    SizedDefinition::I64(Endian::Big).to_i64(Context::new_at(&data, 0))?);
-        assert_eq!(-9223372036854775808i64, SizedDefinition::I64(Endian::Big).to_i64(Context::new_at(&data, 8))?);
+        // Types without an endian, and reading off the end
+        let data = b"\x01\x00\x00\x00".to_vec();
+        assert!(SizedDefinition::U8.guess_endian(Context::new(&data), 1).is_err());
+        assert!(SizedDefinition::ULEB128.guess_endian(Context::new(&data), 1).is_err());
+        assert!(SizedDefinition::U32(Endian::Big).guess_endian(Context::new(&data), 2).is_err());
 
-        assert!(SizedDefinition::I128(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::U8.to_i64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::U16(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::U32(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::U64(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::F32(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
-        assert!(SizedDefinition::F64(Endian::Big).to_i64(Context::new_at(&data, 0)).is_err());
+        assert_eq!(Some(SizedDefinition::U32(Endian::Native)), SizedDefinition::U32(Endian::Big).with_endian(Endian::Native));
+        assert_eq!(None, SizedDefinition::I8.with_endian(Endian::Little));
 
         Ok(())
     }
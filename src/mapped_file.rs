@@ -0,0 +1,123 @@
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+use crate::context::Context;
+use crate::SizedResult;
+
+/// A read-only, memory-mapped file that hands out [`Context`] values.
+///
+/// The operating system pages the file in as it's read, so huge files (like
+/// multi-gigabyte firmware images) can be used without reading them into a
+/// [`Vec`] first:
+///
+/// ```no_run
+/// use sized_number::*;
+///
+/// let file = MappedFile::open("firmware.bin").unwrap();
+/// let d = SizedDefinition::U32(Endian::Little);
+///
+/// println!("{}", d.to_string(file.context_at(0x12345678), SizedDisplay::Decimal).unwrap());
+/// ```
+///
+/// Only available with the `mmap` feature.
+#[derive(Debug)]
+pub struct MappedFile {
+    map: Mmap,
+}
+
+impl MappedFile {
+    /// Open and map a file.
+    ///
+    /// The file must not be changed or truncated (by this process or any
+    /// other) while it's mapped - the operating system doesn't prevent that,
+    /// and reading a truncated mapping can crash.
+    pub fn open<P: AsRef<Path>>(path: P) -> SizedResult<Self> {
+        let file = File::open(path)?;
+
+        // SAFETY: The map is read-only, and the caller is responsible for not
+        // modifying the file underneath us (as documented above)
+        let map = unsafe { Mmap::map(&file) }?;
+
+        Ok(Self { map })
+    }
+
+    /// Get a [`Context`] at the start of the file.
+    pub fn context(&self) -> Context<'_> {
+        Context::new(&self.map)
+    }
+
+    /// Get a [`Context`] at a given position in the file.
+    pub fn context_at(&self, position: u64) -> Context<'_> {
+        Context::new_at(&self.map, position)
+    }
+
+    /// The size of the file, in bytes.
+    pub fn len(&self) -> u64 {
+        self.map.len() as u64
+    }
+
+    /// Returns `true` if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl AsRef<[u8]> for MappedFile {
+    fn as_ref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::{SimpleError, SimpleResult};
+    use std::io::Write;
+
+    use crate::{Endian, SizedDefinition, SizedDisplay};
+
+    /// A temporary file that's removed when the test ends, even if it fails.
+    ///
+    /// Declare it before anything that maps it, so the map is dropped first -
+    /// Windows can't delete a mapped file.
+    struct TempFile {
+        path: std::path::PathBuf,
+    }
+
+    impl TempFile {
+        fn new(name: &str, data: &[u8]) -> SimpleResult<Self> {
+            let path = std::env::temp_dir().join(format!("sized_number_test_{}_{}", name, std::process::id()));
+            let temp = Self { path };
+            File::create(&temp.path).map_err(SimpleError::from)?.write_all(data).map_err(SimpleError::from)?;
+
+            Ok(temp)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    #[test]
+    fn test_mapped_file() -> SimpleResult<()> {
+        let temp = TempFile::new("mapped_file", b"\x00\x01\x02\x03\xff\xff\xff\xff")?;
+        let file = MappedFile::open(&temp.path)?;
+
+        assert_eq!(8, file.len());
+        assert!(!file.is_empty());
+        assert_eq!(0x00010203, file.context().read_u32(Endian::Big)?);
+        assert_eq!(0x0302, Context::new_at(&file, 2).read_u16(Endian::Little)?);
+        assert_eq!("-1", SizedDefinition::I32(Endian::Big).to_string(file.context_at(4), SizedDisplay::Decimal)?);
+        assert_eq!(0x0203, SizedDefinition::U16(Endian::Big).to_u64(file.context_at(2))?);
+        assert!(file.context_at(6).read_u32(Endian::Big).is_err());
+
+        assert!(MappedFile::open(std::env::temp_dir().join("sized_number_test_does_not_exist")).is_err());
+
+        Ok(())
+    }
+}
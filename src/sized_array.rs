@@ -0,0 +1,130 @@
+use half::{bf16, f16};
+
+#[cfg(feature = "serialize")]
+use serde::{Serialize, Deserialize};
+
+use crate::SizedValue;
+use crate::float::{Float80, Float128};
+
+/// Consecutive values of one type, read from a [`crate::Context`].
+///
+/// This is what [`crate::SizedDefinition::read_array`] returns - the
+/// variants map to the variants of [`SizedValue`], but hold a [`Vec`] of
+/// the native type so that big tables can be used (or exported) directly.
+///
+/// Variable-length integers are read into [`SizedArray::U64`] or
+/// [`SizedArray::I64`], just like they read into a [`SizedValue`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SizedArray {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U24(Vec<u32>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    U128(Vec<u128>),
+
+    /// Unsigned integers of any width - the number of bytes, and the values.
+    UN(u8, Vec<u128>),
+
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I24(Vec<i32>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    I128(Vec<i128>),
+
+    /// Signed integers of any width - the number of bytes, and the values.
+    IN(u8, Vec<i128>),
+
+    /// Bitfields, each from its own container - see [`SizedValue::Bits`].
+    Bits {
+        values: Vec<u128>,
+        container: u8,
+        offset: u8,
+        width: u8,
+        signed: bool,
+    },
+
+    F16(Vec<f16>),
+    BF16(Vec<bf16>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    F80(Vec<Float80>),
+    F128(Vec<Float128>),
+}
+
+impl SizedArray {
+    /// The number of values.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::U8(v)   => v.len(),
+            Self::U16(v)  => v.len(),
+            Self::U24(v)  => v.len(),
+            Self::U32(v)  => v.len(),
+            Self::U64(v)  => v.len(),
+            Self::U128(v) => v.len(),
+            Self::UN(_, v) => v.len(),
+
+            Self::I8(v)   => v.len(),
+            Self::I16(v)  => v.len(),
+            Self::I24(v)  => v.len(),
+            Self::I32(v)  => v.len(),
+            Self::I64(v)  => v.len(),
+            Self::I128(v) => v.len(),
+            Self::IN(_, v) => v.len(),
+
+            Self::Bits { values, .. } => values.len(),
+
+            Self::F16(v)  => v.len(),
+            Self::BF16(v) => v.len(),
+            Self::F32(v)  => v.len(),
+            Self::F64(v)  => v.len(),
+            Self::F80(v)  => v.len(),
+            Self::F128(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get one value, as a [`SizedValue`] (so it can be displayed or
+    /// compared), or `None` if `index` is past the end.
+    pub fn get(&self, index: usize) -> Option<SizedValue> {
+        match self {
+            Self::U8(v)   => v.get(index).map(|&v| SizedValue::U8(v)),
+            Self::U16(v)  => v.get(index).map(|&v| SizedValue::U16(v)),
+            Self::U24(v)  => v.get(index).map(|&v| SizedValue::U24(v)),
+            Self::U32(v)  => v.get(index).map(|&v| SizedValue::U32(v)),
+            Self::U64(v)  => v.get(index).map(|&v| SizedValue::U64(v)),
+            Self::U128(v) => v.get(index).map(|&v| SizedValue::U128(v)),
+            Self::UN(bytes, v) => v.get(index).map(|&v| SizedValue::UN(*bytes, v)),
+
+            Self::I8(v)   => v.get(index).map(|&v| SizedValue::I8(v)),
+            Self::I16(v)  => v.get(index).map(|&v| SizedValue::I16(v)),
+            Self::I24(v)  => v.get(index).map(|&v| SizedValue::I24(v)),
+            Self::I32(v)  => v.get(index).map(|&v| SizedValue::I32(v)),
+            Self::I64(v)  => v.get(index).map(|&v| SizedValue::I64(v)),
+            Self::I128(v) => v.get(index).map(|&v| SizedValue::I128(v)),
+            Self::IN(bytes, v) => v.get(index).map(|&v| SizedValue::IN(*bytes, v)),
+
+            Self::Bits { values, container, offset, width, signed } => {
+                values.get(index).map(|&value| SizedValue::Bits { value, container: *container, offset: *offset, width: *width, signed: *signed })
+            },
+
+            Self::F16(v)  => v.get(index).map(|&v| SizedValue::F16(v)),
+            Self::BF16(v) => v.get(index).map(|&v| SizedValue::BF16(v)),
+            Self::F32(v)  => v.get(index).map(|&v| SizedValue::F32(v)),
+            Self::F64(v)  => v.get(index).map(|&v| SizedValue::F64(v)),
+            Self::F80(v)  => v.get(index).map(|&v| SizedValue::F80(v)),
+            Self::F128(v) => v.get(index).map(|&v| SizedValue::F128(v)),
+        }
+    }
+
+    /// Iterate over the values, as [`SizedValue`]s.
+    pub fn iter(&self) -> impl Iterator<Item = SizedValue> + '_ {
+        (0..self.len()).filter_map(move |i| self.get(i))
+    }
+}
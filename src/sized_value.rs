@@ -0,0 +1,641 @@
+use half::{bf16, f16};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[cfg(feature = "serialize")]
+use serde::{Serialize, Deserialize};
+
+use crate::{SizedDisplay, SizedError, SizedResult, write_binary_in_place, write_hex, write_hex_float, write_float_parts, write_decimal, write_octal, write_binary, write_scientific, write_character};
+use crate::context::check_width;
+use crate::float::{self, Decoded, Float80, Float128, FloatFormat};
+
+/// A single value, read from a [`crate::Context`].
+///
+/// This is what [`crate::SizedDefinition::read`] returns - the variants map
+/// to the variants of [`crate::SizedDefinition`], minus the endian (since it
+/// no longer matters once the value is read).
+///
+/// Values of different types can be compared to each other, and compare based
+/// on their numeric value (so `U8(1) == I64(1)`). Comparisons are exact, even
+/// between an integer and a float that can't hold it (so `U64(2^53 + 1)` is
+/// greater than `F64(2^53)`).
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SizedValue {
+    U8(u8),
+    U16(u16),
+    U24(u32),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+
+    /// An unsigned integer of any width - the number of bytes, and the value.
+    UN(u8, u128),
+
+    I8(i8),
+    I16(i16),
+    I24(i32),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+
+    /// A signed integer of any width - the number of bytes, and the value.
+    IN(u8, i128),
+
+    /// A bitfield, read from a [`crate::SizedDefinition::Bits`]. `value` is
+    /// the raw bits of the field (shifted down, but not sign extended); the
+    /// rest describes where it came from.
+    Bits {
+        value: u128,
+        container: u8,
+        offset: u8,
+        width: u8,
+        signed: bool,
+    },
+
+    F16(f16),
+    BF16(bf16),
+    F32(f32),
+    F64(f64),
+    F80(Float80),
+    F128(Float128),
+}
+
+/// An internal function to display any integer type.
+///
+/// Hex, octal, and binary show the raw `bits`, which is `width` bits wide.
+/// Returns `None` (without writing anything) if integers can't be displayed
+/// that way.
+fn write_integer<T>(f: &mut dyn fmt::Write, v: T, bits: u128, width: u32, display: SizedDisplay) -> Option<fmt::Result>
+where
+    T: fmt::Display + fmt::LowerExp,
+{
+    match display {
+        SizedDisplay::Hex(options)        => Some(write_hex(f, bits, width, options)),
+        SizedDisplay::Decimal             => Some(write_decimal(f, v)),
+        SizedDisplay::Octal(options)      => Some(write_octal(f, bits, width, options)),
+        SizedDisplay::Binary(options)     => Some(write_binary(f, bits, width, options)),
+        SizedDisplay::Scientific(options) => Some(write_scientific(f, v, options)),
+        SizedDisplay::FloatParts          => None,
+        SizedDisplay::Character(options)  => Some(write_character(f, bits, options)),
+    }
+}
+
+/// An internal function to display any type of float.
+///
+/// Hex, octal, and binary show the raw `bits`, unless a hex float is
+/// requested. Returns `None` (without writing anything) if floats can't be
+/// displayed that way.
+fn write_float<T>(f: &mut dyn fmt::Write, v: T, bits: u128, format: FloatFormat, display: SizedDisplay) -> Option<fmt::Result>
+where
+    T: fmt::Display + fmt::LowerExp,
+{
+    match display {
+        SizedDisplay::Hex(options) if options.hex_float => Some(write_hex_float(f, bits, format, options)),
+        SizedDisplay::Hex(options)        => Some(write_hex(f, bits, format.width(), options)),
+        SizedDisplay::Decimal             => Some(write_decimal(f, v)),
+        SizedDisplay::Octal(options)      => Some(write_octal(f, bits, format.width(), options)),
+        SizedDisplay::Binary(options)     => Some(write_binary(f, bits, format.width(), options)),
+        SizedDisplay::Scientific(options) => Some(write_scientific(f, v, options)),
+        SizedDisplay::FloatParts          => Some(write_float_parts(f, bits, format)),
+        SizedDisplay::Character(_)        => None,
+    }
+}
+
+impl SizedValue {
+    /// Display the value based on the [`SizedDisplay`].
+    ///
+    /// This is the same as [`crate::SizedDefinition::to_string`], for a value
+    /// that's already been read.
+    pub fn format(self, display: SizedDisplay) -> SizedResult<String> {
+        let mut s = String::new();
+        self.write_to(display, &mut s)?;
+
+        Ok(s)
+    }
+
+    /// Display the value based on the [`SizedDisplay`], writing it to `out`
+    /// instead of building a [`String`].
+    ///
+    /// Nothing is allocated on the heap, except when displaying an
+    /// [`SizedValue::F80`] or [`SizedValue::F128`] in decimal or scientific
+    /// notation (which needs arbitrary precision). If the value can't be
+    /// displayed that way, or is a [`SizedValue::UN`] or [`SizedValue::IN`]
+    /// that isn't 1 to 16 bytes wide, an error is returned and nothing is
+    /// written.
+    ///
+    /// ```
+    /// use sized_number::*;
+    ///
+    /// let mut s = String::from("value: ");
+    /// SizedValue::U16(0x1234).write_to(SizedDisplay::Hex(Default::default()), &mut s).unwrap();
+    ///
+    /// assert_eq!("value: 0x1234", s);
+    /// ```
+    pub fn write_to<W: fmt::Write>(self, display: SizedDisplay, out: &mut W) -> SizedResult<()> {
+        if let Self::UN(bytes, _) | Self::IN(bytes, _) = self {
+            check_width(bytes)?;
+        }
+
+        let f: &mut dyn fmt::Write = out;
+
+        let result = match self {
+            Self::U8(v)   => write_integer(f, v, v as u128, 8, display),
+            Self::U16(v)  => write_integer(f, v, v as u128, 16, display),
+            Self::U24(v)  => write_integer(f, v, v as u128, 24, display),
+            Self::U32(v)  => write_integer(f, v, v as u128, 32, display),
+            Self::U64(v)  => write_integer(f, v, v as u128, 64, display),
+            Self::U128(v) => write_integer(f, v, v, 128, display),
+            Self::UN(bytes, v) => write_integer(f, v, v, bytes as u32 * 8, display),
+
+            // Signed values display their two's complement bits
+            Self::I8(v)   => write_integer(f, v, v as u8 as u128, 8, display),
+            Self::I16(v)  => write_integer(f, v, v as u16 as u128, 16, display),
+            Self::I24(v)  => write_integer(f, v, v as u32 as u128 & 0xffffff, 24, display),
+            Self::I32(v)  => write_integer(f, v, v as u32 as u128, 32, display),
+            Self::I64(v)  => write_integer(f, v, v as u64 as u128, 64, display),
+            Self::I128(v) => write_integer(f, v, v as u128, 128, display),
+            Self::IN(bytes, v) => write_integer(f, v, v as u128 & (u128::MAX >> (128 - bytes as u32 * 8)), bytes as u32 * 8, display),
+
+            Self::Bits { value, container, offset, width, signed } => {
+                match (display, self.sign_magnitude()) {
+                    (SizedDisplay::Binary(options), _) if options.in_place => {
+                        Some(write_binary_in_place(f, value, container as u32 * 8, offset as u32, width as u32, options))
+                    },
+                    (_, Some((true, magnitude))) if signed => write_integer(f, (magnitude as i128).wrapping_neg(), value, width as u32, display),
+                    _ => write_integer(f, value, value, width as u32, display),
+                }
+            },
+
+            Self::F16(v)  => write_float(f, v, v.to_bits() as u128, FloatFormat::F16, display),
+            Self::BF16(v) => write_float(f, v, v.to_bits() as u128, FloatFormat::BF16, display),
+            Self::F32(v)  => write_float(f, v, v.to_bits() as u128, FloatFormat::F32, display),
+            Self::F64(v)  => write_float(f, v, v.to_bits() as u128, FloatFormat::F64, display),
+            Self::F80(v)  => write_float(f, v, v.to_bits(), FloatFormat::F80, display),
+            Self::F128(v) => write_float(f, v, v.to_bits(), FloatFormat::F128, display),
+        };
+
+        match result {
+            Some(result) => Ok(result?),
+            None => Err(SizedError::UnsupportedDisplay { definition: self.type_name(), display }),
+        }
+    }
+
+    /// The name of the value's type, like `u32`, `i40`, or `f80`.
+    pub fn type_name(self) -> String {
+        match self {
+            Self::U8(_)   => "u8".to_string(),
+            Self::U16(_)  => "u16".to_string(),
+            Self::U24(_)  => "u24".to_string(),
+            Self::U32(_)  => "u32".to_string(),
+            Self::U64(_)  => "u64".to_string(),
+            Self::U128(_) => "u128".to_string(),
+            Self::UN(bytes, _) => format!("u{}", bytes as u32 * 8),
+
+            Self::I8(_)   => "i8".to_string(),
+            Self::I16(_)  => "i16".to_string(),
+            Self::I24(_)  => "i24".to_string(),
+            Self::I32(_)  => "i32".to_string(),
+            Self::I64(_)  => "i64".to_string(),
+            Self::I128(_) => "i128".to_string(),
+            Self::IN(bytes, _) => format!("i{}", bytes as u32 * 8),
+
+            Self::Bits { width, signed: false, .. } => format!("u{} bitfield", width),
+            Self::Bits { width, signed: true, .. }  => format!("i{} bitfield", width),
+
+            Self::F16(_)  => "f16".to_string(),
+            Self::BF16(_) => "bf16".to_string(),
+            Self::F32(_)  => "f32".to_string(),
+            Self::F64(_)  => "f64".to_string(),
+            Self::F80(_)  => "f80".to_string(),
+            Self::F128(_) => "f128".to_string(),
+        }
+    }
+
+    /// Returns `true` for the signed integer variants.
+    pub fn is_signed(self) -> bool {
+        matches!(self, Self::I8(_) | Self::I16(_) | Self::I24(_) | Self::I32(_) | Self::I64(_) | Self::I128(_) | Self::IN(_, _) | Self::Bits { signed: true, .. })
+    }
+
+    /// Returns `true` for the floating point variants.
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::F16(_) | Self::BF16(_) | Self::F32(_) | Self::F64(_) | Self::F80(_) | Self::F128(_))
+    }
+
+    /// An internal function to get an integer as a sign and magnitude, which
+    /// lets us compare any two integers without overflowing.
+    fn sign_magnitude(self) -> Option<(bool, u128)> {
+        match self {
+            Self::U8(v)   => Some((false, v as u128)),
+            Self::U16(v)  => Some((false, v as u128)),
+            Self::U24(v)  => Some((false, v as u128)),
+            Self::U32(v)  => Some((false, v as u128)),
+            Self::U64(v)  => Some((false, v as u128)),
+            Self::U128(v) => Some((false, v)),
+            Self::UN(_, v) => Some((false, v)),
+
+            Self::I8(v)   => Some((v < 0, (v as i128).unsigned_abs())),
+            Self::I16(v)  => Some((v < 0, (v as i128).unsigned_abs())),
+            Self::I24(v)  => Some((v < 0, (v as i128).unsigned_abs())),
+            Self::I32(v)  => Some((v < 0, (v as i128).unsigned_abs())),
+            Self::I64(v)  => Some((v < 0, (v as i128).unsigned_abs())),
+            Self::I128(v) => Some((v < 0, v.unsigned_abs())),
+            Self::IN(_, v) => Some((v < 0, v.unsigned_abs())),
+
+            // The sign bit of a field is its top bit (an empty field is zero)
+            Self::Bits { value, width, signed: true, .. } if width > 0 && (value >> (width - 1)) & 1 == 1 => {
+                Some((true, (1u128 << (width - 1) << 1).wrapping_sub(value)))
+            },
+            Self::Bits { value, .. } => Some((false, value)),
+
+            Self::F16(_)  => None,
+            Self::BF16(_) => None,
+            Self::F32(_)  => None,
+            Self::F64(_)  => None,
+            Self::F80(_)  => None,
+            Self::F128(_) => None,
+        }
+    }
+
+    /// An internal function to get any value as a sign, significand, and
+    /// power of two, without losing anything. This is what comparisons use.
+    fn decode(self) -> Decoded {
+        match self {
+            Self::F16(v)  => FloatFormat::F16.decode(v.to_bits() as u128),
+            Self::BF16(v) => FloatFormat::BF16.decode(v.to_bits() as u128),
+            Self::F32(v)  => FloatFormat::F32.decode(v.to_bits() as u128),
+            Self::F64(v)  => FloatFormat::F64.decode(v.to_bits() as u128),
+            Self::F80(v)  => FloatFormat::F80.decode(v.to_bits()),
+            Self::F128(v) => FloatFormat::F128.decode(v.to_bits()),
+            _ => {
+                let (negative, magnitude) = self.sign_magnitude().unwrap_or_default();
+                Decoded::Finite(negative, magnitude, 0)
+            },
+        }
+    }
+
+    /// An internal function to get any value as a [`f64`], possibly losing
+    /// precision. This is only used for guessing endianness.
+    fn as_f64_lossy(self) -> f64 {
+        match self {
+            Self::U8(v)   => v as f64,
+            Self::U16(v)  => v as f64,
+            Self::U24(v)  => v as f64,
+            Self::U32(v)  => v as f64,
+            Self::U64(v)  => v as f64,
+            Self::U128(v) => v as f64,
+            Self::UN(_, v) => v as f64,
+
+            Self::I8(v)   => v as f64,
+            Self::I16(v)  => v as f64,
+            Self::I24(v)  => v as f64,
+            Self::I32(v)  => v as f64,
+            Self::I64(v)  => v as f64,
+            Self::I128(v) => v as f64,
+            Self::IN(_, v) => v as f64,
+            Self::Bits { .. } => {
+                let (negative, magnitude) = self.sign_magnitude().unwrap_or_default();
+                if negative { -(magnitude as f64) } else { magnitude as f64 }
+            },
+
+            Self::F16(v)  => v.to_f64(),
+            Self::BF16(v) => v.to_f64(),
+            Self::F32(v)  => v as f64,
+            Self::F64(v)  => v,
+            Self::F80(v)  => v.to_f64(),
+            Self::F128(v) => v.to_f64(),
+        }
+    }
+
+    /// An internal function to score how plausible a value is, for guessing
+    /// endianness - higher is more plausible.
+    ///
+    /// Integers score by how small they are (the number of leading zero bits
+    /// in their magnitude), plus a little for being aligned like a pointer.
+    /// Floats score one point if they're in a sane range, and zero if they
+    /// are tiny, huge, infinite, or NaN.
+    pub(crate) fn plausibility(self) -> u32 {
+        if self.is_float() {
+            let v = self.as_f64_lossy().abs();
+
+            return match v == 0.0 || (1e-9..=1e12).contains(&v) {
+                true  => 1,
+                false => 0,
+            };
+        }
+
+        match self.sign_magnitude() {
+            Some((false, 0)) => 128,
+            Some((false, magnitude)) => magnitude.leading_zeros() + magnitude.trailing_zeros().min(3),
+            Some((true, magnitude))  => magnitude.leading_zeros(),
+            None => 0,
+        }
+    }
+}
+
+impl fmt::Display for SizedValue {
+    /// Display the value in decimal, the same as [`SizedDisplay::Decimal`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U8(v)   => write!(f, "{}", v),
+            Self::U16(v)  => write!(f, "{}", v),
+            Self::U24(v)  => write!(f, "{}", v),
+            Self::U32(v)  => write!(f, "{}", v),
+            Self::U64(v)  => write!(f, "{}", v),
+            Self::U128(v) => write!(f, "{}", v),
+            Self::UN(_, v) => write!(f, "{}", v),
+
+            Self::I8(v)   => write!(f, "{}", v),
+            Self::I16(v)  => write!(f, "{}", v),
+            Self::I24(v)  => write!(f, "{}", v),
+            Self::I32(v)  => write!(f, "{}", v),
+            Self::I64(v)  => write!(f, "{}", v),
+            Self::I128(v) => write!(f, "{}", v),
+            Self::IN(_, v) => write!(f, "{}", v),
+            Self::Bits { .. } => {
+                let (negative, magnitude) = self.sign_magnitude().unwrap_or_default();
+                write!(f, "{}{}", if negative { "-" } else { "" }, magnitude)
+            },
+
+            Self::F16(v)  => write!(f, "{}", v),
+            Self::BF16(v) => write!(f, "{}", v),
+            Self::F32(v)  => write!(f, "{}", v),
+            Self::F64(v)  => write!(f, "{}", v),
+            Self::F80(v)  => write!(f, "{}", v),
+            Self::F128(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl PartialOrd for SizedValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // Everything is compared exactly, even integers that are too big to
+        // fit in the float they're compared to
+        float::compare(self.decode(), other.decode())
+    }
+}
+
+impl PartialEq for SizedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+macro_rules! impl_from {
+    ($t:ty, $variant:ident) => {
+        impl From<$t> for SizedValue {
+            fn from(v: $t) -> Self {
+                Self::$variant(v)
+            }
+        }
+    };
+}
+
+impl_from!(u8,   U8);
+impl_from!(u16,  U16);
+impl_from!(u32,  U32);
+impl_from!(u64,  U64);
+impl_from!(u128, U128);
+impl_from!(i8,   I8);
+impl_from!(i16,  I16);
+impl_from!(i32,  I32);
+impl_from!(i64,  I64);
+impl_from!(i128, I128);
+impl_from!(f16,  F16);
+impl_from!(bf16, BF16);
+impl_from!(f32,  F32);
+impl_from!(f64,  F64);
+impl_from!(Float80,  F80);
+impl_from!(Float128, F128);
+
+/// Only unsigned values of 64 bits or less can become a [`u64`] - we don't
+/// typecast signed to unsigned.
+impl TryFrom<SizedValue> for u64 {
+    type Error = SizedError;
+
+    fn try_from(v: SizedValue) -> SizedResult<Self> {
+        match v {
+            SizedValue::U8(v)   => Ok(v as u64),
+            SizedValue::U16(v)  => Ok(v as u64),
+            SizedValue::U24(v)  => Ok(v as u64),
+            SizedValue::U32(v)  => Ok(v as u64),
+            SizedValue::U64(v)  => Ok(v),
+            SizedValue::UN(bytes, v) if bytes <= 8 => Ok(v as u64),
+            SizedValue::Bits { value, width, signed: false, .. } if width <= 64 => Ok(value as u64),
+
+            // Signed, too big, or floating point
+            v => Err(SizedError::Conversion { from: v.type_name(), to: "u64" }),
+        }
+    }
+}
+
+/// Any unsigned value can become a [`u128`].
+impl TryFrom<SizedValue> for u128 {
+    type Error = SizedError;
+
+    fn try_from(v: SizedValue) -> SizedResult<Self> {
+        match v {
+            SizedValue::U128(v) => Ok(v),
+            SizedValue::UN(_, v) => Ok(v),
+            SizedValue::Bits { value, signed: false, .. } => Ok(value),
+            v => Ok(u64::try_from(v).map_err(|_| SizedError::Conversion { from: v.type_name(), to: "u128" })? as u128),
+        }
+    }
+}
+
+/// Signed values of 64 bits or less can become an [`i64`], as can unsigned
+/// values of 63 bits or less (since they always fit). We don't typecast
+/// bigger unsigned values to signed. The sign is extended correctly.
+impl TryFrom<SizedValue> for i64 {
+    type Error = SizedError;
+
+    fn try_from(v: SizedValue) -> SizedResult<Self> {
+        match v {
+            SizedValue::I8(v)   => Ok(v as i64),
+            SizedValue::I16(v)  => Ok(v as i64),
+            SizedValue::I24(v)  => Ok(v as i64),
+            SizedValue::I32(v)  => Ok(v as i64),
+            SizedValue::I64(v)  => Ok(v),
+            SizedValue::IN(bytes, v) if bytes <= 8 => Ok(v as i64),
+            SizedValue::Bits { width, signed: true, .. } if width <= 64 => Ok(i128::try_from(v)? as i64),
+
+            // Unsigned values that can't be too big
+            SizedValue::U8(v)   => Ok(v as i64),
+            SizedValue::U16(v)  => Ok(v as i64),
+            SizedValue::U24(v)  => Ok(v as i64),
+            SizedValue::U32(v)  => Ok(v as i64),
+            SizedValue::UN(bytes, v) if bytes < 8 => Ok(v as i64),
+            SizedValue::Bits { value, width, signed: false, .. } if width < 64 => Ok(value as i64),
+
+            // Unsigned and 64 bits or more (we don't typecast), more than 64
+            // bits, or floating point
+            v => Err(SizedError::Conversion { from: v.type_name(), to: "i64" }),
+        }
+    }
+}
+
+/// Any signed value can become an [`i128`], as can unsigned values of 127
+/// bits or less.
+impl TryFrom<SizedValue> for i128 {
+    type Error = SizedError;
+
+    fn try_from(v: SizedValue) -> SizedResult<Self> {
+        match v {
+            SizedValue::I128(v) => Ok(v),
+            SizedValue::IN(_, v) => Ok(v),
+            SizedValue::Bits { signed: true, .. } => {
+                let (negative, magnitude) = v.sign_magnitude().unwrap_or_default();
+                Ok(if negative { (magnitude as i128).wrapping_neg() } else { magnitude as i128 })
+            },
+            SizedValue::U64(v) => Ok(v as i128),
+            SizedValue::UN(bytes, v) if bytes < 16 => Ok(v as i128),
+            SizedValue::Bits { value, width, signed: false, .. } if width < 128 => Ok(value as i128),
+            v => Ok(i64::try_from(v).map_err(|_| SizedError::Conversion { from: v.type_name(), to: "i128" })? as i128),
+        }
+    }
+}
+
+/// Floats of 64 bits or less can become a [`f64`], since that's lossless.
+/// Bigger floats and integers can't (but see [`Float80::to_f64`] and
+/// [`Float128::to_f64`]).
+impl TryFrom<SizedValue> for f64 {
+    type Error = SizedError;
+
+    fn try_from(v: SizedValue) -> SizedResult<Self> {
+        match v {
+            SizedValue::F16(v)  => Ok(v.to_f64()),
+            SizedValue::BF16(v) => Ok(v.to_f64()),
+            SizedValue::F32(v)  => Ok(v as f64),
+            SizedValue::F64(v)  => Ok(v),
+            v => Err(SizedError::Conversion { from: v.type_name(), to: "f64" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::SimpleResult;
+
+    #[test]
+    fn test_display() -> SimpleResult<()> {
+        assert_eq!("255",                  SizedValue::U8(255).to_string());
+        assert_eq!("-1",                   SizedValue::I8(-1).to_string());
+        assert_eq!("-9223372036854775808", SizedValue::I64(i64::MIN).to_string());
+        assert_eq!("25",                   SizedValue::F32(25.0).to_string());
+        assert_eq!("3.15",                 SizedValue::F64(3.15).to_string());
+        assert_eq!("NaN",                  SizedValue::F64(f64::NAN).to_string());
+        assert_eq!("0",                    SizedValue::Bits { value: 0, container: 1, offset: 0, width: 0, signed: true }.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format() -> SimpleResult<()> {
+        assert_eq!("0x00ab",   SizedValue::U16(0xab).format(SizedDisplay::Hex(Default::default()))?);
+        assert_eq!("0xff",     SizedValue::I8(-1).format(SizedDisplay::Hex(Default::default()))?);
+        assert_eq!("0xffffff", SizedValue::I24(-1).format(SizedDisplay::Hex(Default::default()))?);
+        assert_eq!("0x00000000ff", SizedValue::IN(5, 255).format(SizedDisplay::Hex(Default::default()))?);
+        assert_eq!("-1",       SizedValue::I8(-1).format(SizedDisplay::Decimal)?);
+        assert_eq!("1e2",      SizedValue::U32(100).format(SizedDisplay::Scientific(Default::default()))?);
+        assert_eq!("3.15E0",   SizedValue::F64(3.15).format(SizedDisplay::Scientific(crate::ScientificOptions { uppercase: true }))?);
+
+        assert_eq!("0x3f800000", SizedValue::F32(1.0).format(SizedDisplay::Hex(Default::default()))?);
+
+        assert!(SizedValue::U8(1).format(SizedDisplay::FloatParts).is_err());
+        assert!(SizedValue::I128(1).format(SizedDisplay::FloatParts).is_err());
+        assert_eq!("\\n",      SizedValue::U8(10).format(SizedDisplay::Character(Default::default()))?);
+        assert!(SizedValue::F32(10.0).format(SizedDisplay::Character(Default::default())).is_err());
+
+        // Widths that can't exist
+        for bytes in [0, 17, 20] {
+            let mut s = String::new();
+            assert!(SizedValue::UN(bytes, 5).write_to(SizedDisplay::Decimal, &mut s).is_err());
+            assert!(SizedValue::IN(bytes, 5).write_to(SizedDisplay::Decimal, &mut s).is_err());
+            assert!(SizedValue::IN(bytes, 5).format(SizedDisplay::Hex(Default::default())).is_err());
+            assert_eq!("", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare() -> SimpleResult<()> {
+        // Same type
+        assert!(SizedValue::U8(1) < SizedValue::U8(2));
+        assert!(SizedValue::I32(-2) < SizedValue::I32(1));
+
+        // Different types, including ones that don't fit in each other
+        assert_eq!(SizedValue::U8(1), SizedValue::I64(1));
+        assert!(SizedValue::I8(-1) < SizedValue::U8(0));
+        assert!(SizedValue::I128(i128::MIN) < SizedValue::I8(-128));
+        assert!(SizedValue::U128(u128::MAX) > SizedValue::I128(i128::MAX));
+        assert_eq!(SizedValue::Bits { value: 0x1f, container: 1, offset: 0, width: 5, signed: true }, SizedValue::I8(-1));
+        assert_eq!(SizedValue::Bits { value: 0x1f, container: 1, offset: 0, width: 5, signed: false }, SizedValue::U8(31));
+        assert_eq!(SizedValue::Bits { value: 0, container: 1, offset: 0, width: 0, signed: true }, SizedValue::U8(0));
+        assert!(SizedValue::U64(u64::MAX) > SizedValue::I64(-1));
+
+        // Floats
+        assert_eq!(SizedValue::F32(25.0), SizedValue::U8(25));
+        assert_eq!(SizedValue::F16(f16::from_f32(1.5)), SizedValue::BF16(bf16::from_f32(1.5)));
+        assert!(SizedValue::F64(3.15) > SizedValue::I16(3));
+        assert!(SizedValue::F64(-0.5) < SizedValue::U8(0));
+        assert_ne!(SizedValue::F64(f64::NAN), SizedValue::F64(f64::NAN));
+        assert_eq!(None, SizedValue::F32(f32::NAN).partial_cmp(&SizedValue::U8(0)));
+        assert_eq!(SizedValue::F80(Float80::from_bits(0x3fff8000000000000000)), SizedValue::U8(1));
+        assert!(SizedValue::F128(Float128::from_bits(0x40000000000000000000000000000000)) > SizedValue::F64(1.5));
+        assert_eq!(SizedValue::F64(-0.0), SizedValue::U8(0));
+        assert!(SizedValue::F64(f64::INFINITY) > SizedValue::U128(u128::MAX));
+        assert!(SizedValue::F32(f32::NEG_INFINITY) < SizedValue::F64(f64::MIN));
+
+        // Integers that don't fit exactly in the float aren't equal to it
+        assert_eq!(SizedValue::F64(9007199254740992.0), SizedValue::U64(1 << 53));
+        assert_ne!(SizedValue::F64(9007199254740992.0), SizedValue::U64((1 << 53) + 1));
+        assert!(SizedValue::U64((1 << 53) + 1) > SizedValue::F64(9007199254740992.0));
+        assert!(SizedValue::I64(-(1 << 53) - 1) < SizedValue::F64(-9007199254740992.0));
+        assert!(SizedValue::U128(u128::MAX) < SizedValue::F64(340282366920938463463374607431768211456.0));
+        assert!(SizedValue::U128(u128::MAX) > SizedValue::F32(f32::MAX));
+        assert_eq!(SizedValue::F80(Float80::from_bits(0x403effffffffffffffff)), SizedValue::U64(u64::MAX));
+        assert!(SizedValue::F80(Float80::from_bits(0x403effffffffffffffff)) > SizedValue::F64(18446744073709549568.0));
+        assert!(SizedValue::F64(0.5) > SizedValue::F128(Float128::from_bits(0x3ffdffffffffffffffffffffffffffff)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversions() -> SimpleResult<()> {
+        assert_eq!(SizedValue::U16(1), SizedValue::from(1u16));
+        assert_eq!(SizedValue::F64(1.5), SizedValue::from(1.5f64));
+
+        assert_eq!(255u64,                u64::try_from(SizedValue::U8(255))?);
+        assert_eq!(u64::MAX,              u64::try_from(SizedValue::U64(u64::MAX))?);
+        assert_eq!(u128::MAX,             u128::try_from(SizedValue::U128(u128::MAX))?);
+        assert_eq!(255u128,               u128::try_from(SizedValue::U8(255))?);
+        assert_eq!(-1i64,                 i64::try_from(SizedValue::I8(-1))?);
+        assert_eq!(-1i128,                i128::try_from(SizedValue::I32(-1))?);
+        assert_eq!(25f64,                 f64::try_from(SizedValue::F32(25.0))?);
+        assert_eq!(1.5f64,                f64::try_from(SizedValue::F16(f16::from_f32(1.5)))?);
+
+        assert!(u64::try_from(SizedValue::U128(0)).is_err());
+        assert!(u64::try_from(SizedValue::I8(1)).is_err());
+        assert!(u128::try_from(SizedValue::I128(1)).is_err());
+        assert_eq!(255i64,                i64::try_from(SizedValue::U8(255))?);
+        assert_eq!(0xffffffffi64,         i64::try_from(SizedValue::U32(u32::MAX))?);
+        assert_eq!(0xffffffffffffffi64,   i64::try_from(SizedValue::UN(7, 0xffffffffffffff))?);
+        assert_eq!(0x7fffffffffffffffi64, i64::try_from(SizedValue::Bits { value: 0x7fffffffffffffff, container: 8, offset: 0, width: 63, signed: false })?);
+        assert_eq!(u64::MAX as i128,      i128::try_from(SizedValue::U64(u64::MAX))?);
+        assert!(i64::try_from(SizedValue::U64(1)).is_err());
+        assert!(i64::try_from(SizedValue::UN(8, 1)).is_err());
+        assert!(i64::try_from(SizedValue::Bits { value: 1, container: 8, offset: 0, width: 64, signed: false }).is_err());
+        assert!(i128::try_from(SizedValue::U128(1)).is_err());
+        assert!(i64::try_from(SizedValue::I128(1)).is_err());
+        assert!(i128::try_from(SizedValue::F64(1.0)).is_err());
+        assert!(f64::try_from(SizedValue::U8(1)).is_err());
+        assert!(f64::try_from(SizedValue::F80(Float80::from_bits(0))).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,485 @@
+use half::{bf16, f16};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom};
+use std::rc::Rc;
+
+use crate::context::{Context, Endian, MAX_UTF8_BYTES, MAX_UTF16_WORDS, MAX_VARINT_BYTES};
+use crate::float::{Float80, Float128};
+use crate::{SizedDefinition, SizedDisplay, SizedError, SizedResult, SizedValue};
+
+/// The default size of a cached page, in bytes
+pub const DEFAULT_PAGE_SIZE: usize = 4096;
+
+/// The default number of pages to cache
+pub const DEFAULT_CACHE_PAGES: usize = 64;
+
+/// A data source that isn't in memory - anything that implements [`Read`]
+/// and [`Seek`], like a [`std::fs::File`] or a decompressing reader.
+///
+/// Data is read a page at a time, and the most recently used pages are
+/// cached. Use [`Stream::context`] or [`Stream::context_at`] to get a
+/// [`StreamContext`], which has the same API as [`Context`]:
+///
+/// ```
+/// use sized_number::*;
+/// use std::io::Cursor;
+///
+/// let stream = Stream::new(Cursor::new(b"\x00\x01\x02\x03".to_vec())).unwrap();
+///
+/// assert_eq!(0x0203, stream.context_at(2).read_u16(Endian::Big).unwrap());
+/// assert_eq!("0x00010203", stream.context().to_string(SizedDefinition::U32(Endian::Big), SizedDisplay::Hex(Default::default())).unwrap());
+/// ```
+pub struct Stream<R: Read + Seek> {
+    reader: RefCell<R>,
+    len: u64,
+    page_size: usize,
+    cache_pages: usize,
+
+    /// The cached pages, most recently used first - shared, so a cache hit
+    /// doesn't copy the whole page
+    cache: RefCell<Vec<(u64, Rc<[u8]>)>>,
+}
+
+impl<R: Read + Seek> fmt::Debug for Stream<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stream")
+            .field("len", &self.len)
+            .field("page_size", &self.page_size)
+            .field("cache_pages", &self.cache_pages)
+            .finish()
+    }
+}
+
+impl<R: Read + Seek> Stream<R> {
+    /// Create a new [`Stream`] with the default cache size.
+    ///
+    /// This seeks to the end of the reader to find its length.
+    pub fn new(reader: R) -> SizedResult<Self> {
+        Self::with_cache(reader, DEFAULT_PAGE_SIZE, DEFAULT_CACHE_PAGES)
+    }
+
+    /// Create a new [`Stream`] that caches up to `cache_pages` pages of
+    /// `page_size` bytes each.
+    pub fn with_cache(mut reader: R, page_size: usize, cache_pages: usize) -> SizedResult<Self> {
+        if page_size == 0 || cache_pages == 0 {
+            return Err(SizedError::InvalidArgument("Stream cache can't be empty".to_string()));
+        }
+
+        let len = reader.seek(SeekFrom::End(0))?;
+
+        Ok(Self {
+            reader: RefCell::new(reader),
+            len,
+            page_size,
+            cache_pages,
+            cache: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// The length of the stream, in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the stream is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a [`StreamContext`] at the start of the stream.
+    pub fn context(&self) -> StreamContext<'_, R> {
+        self.context_at(0)
+    }
+
+    /// Get a [`StreamContext`] at a given position in the stream.
+    pub fn context_at(&self, position: u64) -> StreamContext<'_, R> {
+        StreamContext {
+            stream: self,
+            position,
+        }
+    }
+
+    /// An internal function to read one page from the reader, or get it from
+    /// the cache.
+    fn page(&self, index: u64) -> SizedResult<Rc<[u8]>> {
+        let mut cache = self.cache.borrow_mut();
+
+        if let Some(i) = cache.iter().position(|(cached, _)| *cached == index) {
+            let entry = cache.remove(i);
+            cache.insert(0, entry);
+
+            return Ok(Rc::clone(&cache[0].1));
+        }
+
+        // Read until the page is full, or the stream ends
+        let mut reader = self.reader.borrow_mut();
+        reader.seek(SeekFrom::Start(index * self.page_size as u64))?;
+
+        let mut page = vec![0; self.page_size];
+        let mut filled = 0;
+        while filled < page.len() {
+            match reader.read(&mut page[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(SizedError::from(e)),
+            }
+        }
+        page.truncate(filled);
+
+        let page: Rc<[u8]> = Rc::from(page);
+        cache.insert(0, (index, Rc::clone(&page)));
+        cache.truncate(self.cache_pages);
+
+        Ok(page)
+    }
+
+    /// An internal function to read up to `size` bytes at `position` - fewer
+    /// if the stream ends first.
+    fn read_up_to(&self, position: u64, size: usize) -> SizedResult<Vec<u8>> {
+        // Never allocate more than the stream can actually provide
+        let available = self.len.saturating_sub(position);
+        let mut v: Vec<u8> = Vec::with_capacity(usize::try_from(available).map_or(size, |available| available.min(size)));
+
+        while v.len() < size {
+            let position = match position.checked_add(v.len() as u64) {
+                Some(p) if p < self.len => p,
+                _ => break,
+            };
+
+            let page = self.page(position / self.page_size as u64)?;
+            let start = (position % self.page_size as u64) as usize;
+            if start >= page.len() {
+                break;
+            }
+
+            let end = page.len().min(start + (size - v.len()));
+            v.extend_from_slice(&page[start..end]);
+        }
+
+        Ok(v)
+    }
+}
+
+/// A position in a [`Stream`] - the streaming counterpart to [`Context`].
+///
+/// Like [`Context`], this is cheap to copy, and reading doesn't change the
+/// position. Each read fetches just the bytes it needs (through the
+/// stream's cache) and decodes them the same way [`Context`] does, so
+/// values and errors are identical.
+pub struct StreamContext<'a, R: Read + Seek> {
+    stream: &'a Stream<R>,
+    position: u64,
+}
+
+// Deriving these would require R to be Clone / Copy / Debug
+impl<'a, R: Read + Seek> Clone for StreamContext<'a, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, R: Read + Seek> Copy for StreamContext<'a, R> {}
+
+impl<'a, R: Read + Seek> fmt::Debug for StreamContext<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamContext")
+            .field("stream", self.stream)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl<'a, R: Read + Seek> StreamContext<'a, R> {
+    /// Copy the [`StreamContext`] and change the position at the same time.
+    pub fn at(self, new_position: u64) -> Self {
+        let mut c = self;
+        c.position = new_position;
+
+        c
+    }
+
+    /// Get the current position.
+    pub fn position(self) -> u64 {
+        self.position
+    }
+
+    /// An internal function to fetch up to `size` bytes, then read them with
+    /// a [`Context`].
+    ///
+    /// Errors are moved to the stream's position, so they match what a
+    /// [`Context`] over the whole stream would return.
+    fn with_context<T>(self, size: usize, f: impl FnOnce(Context) -> SizedResult<T>) -> SizedResult<T> {
+        let v = self.stream.read_up_to(self.position, size)?;
+
+        f(Context::new(&v)).map_err(|e| match e.offset_by(self.position) {
+            SizedError::OutOfBounds { position, needed, .. } => SizedError::OutOfBounds {
+                position,
+                needed,
+                available: self.stream.len.saturating_sub(position),
+            },
+            e => e,
+        })
+    }
+
+    pub fn read_u8(self) -> SizedResult<u8> {
+        self.with_context(1, |c| c.read_u8())
+    }
+
+    pub fn read_u16(self, endian: Endian) -> SizedResult<u16> {
+        self.with_context(2, |c| c.read_u16(endian))
+    }
+
+    pub fn read_u24(self, endian: Endian) -> SizedResult<u32> {
+        self.with_context(3, |c| c.read_u24(endian))
+    }
+
+    pub fn read_u32(self, endian: Endian) -> SizedResult<u32> {
+        self.with_context(4, |c| c.read_u32(endian))
+    }
+
+    pub fn read_u64(self, endian: Endian) -> SizedResult<u64> {
+        self.with_context(8, |c| c.read_u64(endian))
+    }
+
+    pub fn read_u128(self, endian: Endian) -> SizedResult<u128> {
+        self.with_context(16, |c| c.read_u128(endian))
+    }
+
+    pub fn read_uint(self, bytes: u8, endian: Endian) -> SizedResult<u128> {
+        self.with_context(bytes as usize, |c| c.read_uint(bytes, endian))
+    }
+
+    pub fn read_i8(self) -> SizedResult<i8> {
+        self.with_context(1, |c| c.read_i8())
+    }
+
+    pub fn read_i16(self, endian: Endian) -> SizedResult<i16> {
+        self.with_context(2, |c| c.read_i16(endian))
+    }
+
+    pub fn read_i24(self, endian: Endian) -> SizedResult<i32> {
+        self.with_context(3, |c| c.read_i24(endian))
+    }
+
+    pub fn read_i32(self, endian: Endian) -> SizedResult<i32> {
+        self.with_context(4, |c| c.read_i32(endian))
+    }
+
+    pub fn read_i64(self, endian: Endian) -> SizedResult<i64> {
+        self.with_context(8, |c| c.read_i64(endian))
+    }
+
+    pub fn read_i128(self, endian: Endian) -> SizedResult<i128> {
+        self.with_context(16, |c| c.read_i128(endian))
+    }
+
+    pub fn read_int(self, bytes: u8, endian: Endian) -> SizedResult<i128> {
+        self.with_context(bytes as usize, |c| c.read_int(bytes, endian))
+    }
+
+    pub fn read_f16(self, endian: Endian) -> SizedResult<f16> {
+        self.with_context(2, |c| c.read_f16(endian))
+    }
+
+    pub fn read_bf16(self, endian: Endian) -> SizedResult<bf16> {
+        self.with_context(2, |c| c.read_bf16(endian))
+    }
+
+    pub fn read_f32(self, endian: Endian) -> SizedResult<f32> {
+        self.with_context(4, |c| c.read_f32(endian))
+    }
+
+    pub fn read_f64(self, endian: Endian) -> SizedResult<f64> {
+        self.with_context(8, |c| c.read_f64(endian))
+    }
+
+    pub fn read_f80(self, endian: Endian) -> SizedResult<Float80> {
+        self.with_context(10, |c| c.read_f80(endian))
+    }
+
+    pub fn read_f128(self, endian: Endian) -> SizedResult<Float128> {
+        self.with_context(16, |c| c.read_f128(endian))
+    }
+
+    pub fn read_uleb128(self) -> SizedResult<(usize, u64)> {
+        self.with_context(MAX_VARINT_BYTES, |c| c.read_uleb128())
+    }
+
+    pub fn read_sleb128(self) -> SizedResult<(usize, i64)> {
+        self.with_context(MAX_VARINT_BYTES, |c| c.read_sleb128())
+    }
+
+    pub fn read_zigzag(self) -> SizedResult<(usize, i64)> {
+        self.with_context(MAX_VARINT_BYTES, |c| c.read_zigzag())
+    }
+
+    pub fn read_git_varint(self) -> SizedResult<(usize, u64)> {
+        self.with_context(MAX_VARINT_BYTES, |c| c.read_git_varint())
+    }
+
+    pub fn read_bytes(self, size: usize) -> SizedResult<Vec<u8>> {
+        // Check the size before reading anything, since it might have come
+        // from untrusted data
+        let available = self.stream.len.saturating_sub(self.position);
+        if size as u64 > available {
+            return Err(SizedError::OutOfBounds {
+                position: self.position,
+                needed: size as u64,
+                available,
+            });
+        }
+
+        let v = self.stream.read_up_to(self.position, size)?;
+        if v.len() < size {
+            // The reader ended early
+            return Err(SizedError::OutOfBounds {
+                position: self.position,
+                needed: size as u64,
+                available: v.len() as u64,
+            });
+        }
+
+        Ok(v)
+    }
+
+    pub fn read_utf8(self) -> SizedResult<(usize, char)> {
+        self.with_context(MAX_UTF8_BYTES, |c| c.read_utf8())
+    }
+
+    pub fn read_utf16(self, endian: Endian) -> SizedResult<(usize, char)> {
+        self.with_context(MAX_UTF16_WORDS * 2, |c| c.read_utf16(endian))
+    }
+
+    pub fn read_utf32(self, endian: Endian) -> SizedResult<char> {
+        self.with_context(4, |c| c.read_utf32(endian))
+    }
+
+    /// Read a value, based on a [`SizedDefinition`] - see
+    /// [`SizedDefinition::read`].
+    pub fn read_definition(self, definition: SizedDefinition) -> SizedResult<SizedValue> {
+        let size = definition.fixed_size().unwrap_or(MAX_VARINT_BYTES as u64);
+
+        self.with_context(size as usize, |c| definition.read(c))
+    }
+
+    /// Read a value and display it - see [`SizedDefinition::to_string`].
+    pub fn to_string(self, definition: SizedDefinition, display: SizedDisplay) -> SizedResult<String> {
+        self.read_definition(definition)?.format(display)
+    }
+
+    /// Read a value as a [`u64`] - see [`SizedDefinition::to_u64`].
+    pub fn to_u64(self, definition: SizedDefinition) -> SizedResult<u64> {
+        u64::try_from(self.read_definition(definition)?)
+    }
+
+    /// Read a value as an [`i64`] - see [`SizedDefinition::to_i64`].
+    pub fn to_i64(self, definition: SizedDefinition) -> SizedResult<i64> {
+        i64::try_from(self.read_definition(definition)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use simple_error::SimpleResult;
+    use std::io::Cursor;
+
+    /// A reader that only returns a couple bytes at a time, and counts how
+    /// often it's read
+    struct SlowReader {
+        inner: Cursor<Vec<u8>>,
+        reads: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl Read for SlowReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.reads.set(self.reads.get() + 1);
+
+            let len = buf.len().min(2);
+            self.inner.read(&mut buf[..len])
+        }
+    }
+
+    impl Seek for SlowReader {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_context() -> SimpleResult<()> {
+        let data: Vec<u8> = (0..=255).map(|i: u32| (i * 37 % 256) as u8).collect();
+        let context = Context::new(&data);
+
+        // Tiny pages, so most values cross a page boundary
+        let stream = Stream::with_cache(Cursor::new(data.clone()), 3, 2)?;
+        assert_eq!(256, stream.len());
+
+        for i in 0..=(data.len() as u64) {
+            let c = context.at(i);
+            let s = stream.context_at(i);
+
+            assert_eq!(c.read_u8(),                 s.read_u8());
+            assert_eq!(c.read_u24(Endian::Little),  s.read_u24(Endian::Little));
+            assert_eq!(c.read_i32(Endian::Big),     s.read_i32(Endian::Big));
+            assert_eq!(c.read_u64(Endian::Little),  s.read_u64(Endian::Little));
+            assert_eq!(c.read_int(5, Endian::Big),  s.read_int(5, Endian::Big));
+            assert_eq!(c.read_u128(Endian::Big),    s.read_u128(Endian::Big));
+            assert_eq!(c.read_uleb128(),            s.read_uleb128());
+            assert_eq!(c.read_utf8(),               s.read_utf8());
+            assert_eq!(c.read_bytes(7),             s.read_bytes(7));
+            assert_eq!(c.read_bytes(usize::MAX),    s.read_bytes(usize::MAX));
+
+            for definition in [SizedDefinition::F64(Endian::Big), SizedDefinition::I16(Endian::Little), SizedDefinition::SLEB128] {
+                assert_eq!(definition.to_string(c, SizedDisplay::Decimal), s.to_string(definition, SizedDisplay::Decimal));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_cache() -> SimpleResult<()> {
+        let reads = std::rc::Rc::new(std::cell::Cell::new(0));
+        let reader = SlowReader {
+            inner: Cursor::new(b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09".to_vec()),
+            reads: reads.clone(),
+        };
+        let stream = Stream::with_cache(reader, 4, 2)?;
+
+        // Short reads are stitched together, across pages
+        assert_eq!(0x0203040506, stream.context_at(2).read_uint(5, Endian::Big)?);
+        let after_first = reads.get();
+
+        // Both pages are cached now
+        assert_eq!(0x0304, stream.context_at(3).read_u16(Endian::Big)?);
+        assert_eq!(0x0607, stream.context_at(6).read_u16(Endian::Big)?);
+        assert_eq!(after_first, reads.get());
+
+        // The last page is short, and pushes out the oldest one
+        assert_eq!(0x0809, stream.context_at(8).read_u16(Endian::Big)?);
+        assert!(stream.context_at(9).read_u16(Endian::Big).is_err());
+        assert!(reads.get() > after_first);
+
+        let after_last = reads.get();
+        assert_eq!(0x0001, stream.context().read_u16(Endian::Big)?);
+        assert!(reads.get() > after_last);
+
+        assert_eq!(0x0809, stream.context_at(8).to_u64(SizedDefinition::U16(Endian::Big))?);
+        assert_eq!(-1, Stream::new(Cursor::new(vec![0xff]))?.context().to_i64(SizedDefinition::I8)?);
+        assert!(Stream::with_cache(Cursor::new(vec![]), 0, 1).is_err());
+
+        // Huge sizes fail before anything is allocated
+        assert_eq!(
+            Err(SizedError::OutOfBounds { position: 1, needed: usize::MAX as u64, available: 2 }),
+            Stream::new(Cursor::new(vec![1, 2, 3]))?.context_at(1).read_bytes(usize::MAX),
+        );
+
+        Ok(())
+    }
+}